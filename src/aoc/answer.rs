@@ -0,0 +1,80 @@
+use std::fmt;
+
+/// The answer produced by a single part of a day, regardless of the
+/// numeric type or string format the solution works with internally.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Empty,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Empty => write!(f, "-"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::Empty, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_share_one_representation() {
+        assert_eq!(Answer::from(42_i32), Answer::from(42_usize));
+        assert_eq!(Answer::from(42_i64), Answer::Number(42));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(-7).to_string(), "-7");
+        assert_eq!(Answer::from("6,1").to_string(), "6,1");
+        assert_eq!(Answer::from(None::<String>).to_string(), "-");
+    }
+
+    #[test]
+    fn test_option_conversion() {
+        assert_eq!(Answer::from(Some(3_usize)), Answer::Number(3));
+        assert_eq!(Answer::from(None::<usize>), Answer::Empty);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::aoc::inputs;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// A named puzzle example of a day, with the answers the puzzle text gives for it.
+///
+/// The examples of a day live in `input/dayN/examples.txt`. Each one starts with a header
+/// line `== <name> [part<N>=<answer>...] [<param>=<value>...]` and is followed by the example
+/// input, up to the next header. Keys other than `part<N>` override a parameter of the day.
+/// Lines starting with `#` are comments, but only before the first header, since `#` is a
+/// common character in puzzle inputs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub answers: BTreeMap<u8, String>,
+    pub params: BTreeMap<String, String>,
+}
+
+pub fn path(day: u8) -> PathBuf {
+    inputs::day_dir(day).join("examples.txt")
+}
+
+/// Loads the examples of a day in file order; a day without an examples file has none.
+pub fn load(day: u8) -> Result<Vec<Example>, String> {
+    let path = path(day);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let text = fs::read_to_string(&path)
+        .map_err(|err| format!("failed to read examples file {}: {}", path.display(), err))?;
+    parse(&text).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+pub fn parse(text: &str) -> Result<Vec<Example>, String> {
+    let mut examples: Vec<Example> = Vec::new();
+    let mut body: Vec<&str> = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        if let Some(header) = line.strip_prefix("== ") {
+            if let Some(example) = examples.last_mut() {
+                example.input = join_body(&body);
+            }
+            body.clear();
+
+            let example =
+                parse_header(header).map_err(|err| format!("line {}: {}", index + 1, err))?;
+            if examples.iter().any(|other| other.name == example.name) {
+                return Err(format!(
+                    "line {}: duplicate example {}",
+                    index + 1,
+                    example.name
+                ));
+            }
+            examples.push(example);
+        } else if !examples.is_empty() {
+            body.push(line);
+        } else if !line.trim().is_empty() && !line.starts_with('#') {
+            return Err(format!(
+                "line {}: expected `== <name>` before the first example",
+                index + 1
+            ));
+        }
+    }
+
+    if let Some(example) = examples.last_mut() {
+        example.input = join_body(&body);
+    }
+
+    Ok(examples)
+}
+
+fn parse_header(header: &str) -> Result<Example, String> {
+    let mut tokens = header.split_whitespace();
+    let name = tokens
+        .next()
+        .filter(|name| !name.contains('='))
+        .ok_or("expected an example name after `==`")?;
+
+    let mut example = Example {
+        name: name.to_string(),
+        ..Example::default()
+    };
+
+    for token in tokens {
+        let (key, value) = token
+            .split_once('=')
+            .ok_or_else(|| format!("expected `<key>=<value>`, found `{}`", token))?;
+
+        match key.strip_prefix("part").map(str::parse::<u8>) {
+            Some(Ok(part)) => example.answers.insert(part, value.to_string()),
+            Some(Err(_)) => return Err(format!("invalid part `{}`", key)),
+            None => example.params.insert(key.to_string(), value.to_string()),
+        };
+    }
+
+    Ok(example)
+}
+
+/// The example input with the blank lines separating it from the next header removed.
+fn join_body(lines: &[&str]) -> String {
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |last| last + 1);
+
+    lines[..end]
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "# comment\n\n== small part1=140 part2=80\nAAAA\nBBCD\n\n== large part1=1930 width=10\n#.#\n\n# not a comment\n";
+        let examples = parse(text).unwrap();
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "small");
+        assert_eq!(examples[0].input, "AAAA\nBBCD\n");
+        assert_eq!(examples[0].answers.get(&1).map(String::as_str), Some("140"));
+        assert_eq!(examples[0].answers.get(&2).map(String::as_str), Some("80"));
+        assert!(examples[0].params.is_empty());
+
+        assert_eq!(examples[1].input, "#.#\n\n# not a comment\n");
+        assert_eq!(examples[1].answers.get(&2).map(String::as_str), None);
+        assert_eq!(
+            examples[1].params.get("width").map(String::as_str),
+            Some("10")
+        );
+    }
+
+    #[test]
+    fn test_parse_keeps_blank_lines_inside_an_example() {
+        let examples = parse("== example part1=4,6,3\nRegister A: 729\n\nProgram: 0,1\n").unwrap();
+        assert_eq!(examples[0].input, "Register A: 729\n\nProgram: 0,1\n");
+        assert_eq!(
+            examples[0].answers.get(&1).map(String::as_str),
+            Some("4,6,3")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_files() {
+        assert!(parse("3 4\n== example\n").is_err());
+        assert!(parse("== \n").is_err());
+        assert!(parse("== example part1\n").is_err());
+        assert!(parse("== example partx=1\n").is_err());
+        assert!(parse("== example\n1\n== example\n2\n").is_err());
+    }
+
+    #[test]
+    fn test_every_day_has_an_example() {
+        for day in 1..=25 {
+            let examples = load(day).unwrap();
+            assert_eq!(
+                examples.first().map(|example| example.name.as_str()),
+                Some(inputs::EXAMPLE),
+                "day {}",
+                day
+            );
+        }
+    }
+}
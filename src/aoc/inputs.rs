@@ -0,0 +1,129 @@
+use crate::aoc::examples::{self, Example};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Environment variable that overrides the directory holding the `dayN` input folders.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Name of the main example of every day, the first one in its examples file.
+pub const EXAMPLE: &str = "example";
+
+/// Files in a day's input folder that are not personal puzzle inputs.
+const RESERVED: [&str; 2] = ["answers", "examples"];
+
+/// The input root: `$AOC_INPUT_DIR` when set, otherwise the `input` folder of this crate,
+/// so inputs resolve the same way whatever the working directory is.
+pub fn root() -> PathBuf {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("input"),
+    }
+}
+
+pub fn day_dir(day: u8) -> PathBuf {
+    root().join(format!("day{}", day))
+}
+
+/// Path of a personal input of a day, e.g. `input/day6/alice.txt` for `alice`.
+pub fn path(day: u8, name: &str) -> PathBuf {
+    day_dir(day).join(format!("{}.txt", name))
+}
+
+/// Reads a named input of a day: a personal input file, or else one of the day's examples.
+pub fn read(day: u8, name: &str) -> Result<String, String> {
+    if let Some(example) = example(day, name)? {
+        return Ok(example.input);
+    }
+
+    let path = path(day, name);
+    fs::read_to_string(&path)
+        .map_err(|err| format!("failed to read input file {}: {}", path.display(), err))
+}
+
+/// Parameter overrides of a named input: those of the example it names, and none for a
+/// personal input.
+pub fn params(day: u8, name: &str) -> Result<BTreeMap<String, String>, String> {
+    Ok(example(day, name)?
+        .map(|example| example.params)
+        .unwrap_or_default())
+}
+
+/// The example called `name`, unless a personal input file of that name shadows it.
+fn example(day: u8, name: &str) -> Result<Option<Example>, String> {
+    if path(day, name).exists() {
+        return Ok(None);
+    }
+    Ok(examples::load(day)?
+        .into_iter()
+        .find(|example| example.name == name))
+}
+
+/// Names of every input of a day: its examples in file order, then its personal inputs.
+pub fn names(day: u8) -> Vec<String> {
+    let mut names: Vec<String> = examples::load(day)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|example| example.name)
+        .collect();
+    names.extend(personal(day));
+    names
+}
+
+/// Names of the personal puzzle inputs of a day in alphabetical order.
+pub fn personal(day: u8) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(day_dir(day)) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .filter(|name| !RESERVED.contains(&name.as_str()))
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let path = path(6, "alice");
+        assert!(path.ends_with("day6/alice.txt"));
+    }
+
+    #[test]
+    fn test_names() {
+        let names = names(12);
+        assert_eq!(names[..2], ["example", "small"]);
+        assert!(names.contains(&"input".to_string()));
+        assert!(!names.contains(&"answers".to_string()));
+        assert!(!names.contains(&"examples".to_string()));
+        assert_eq!(personal(12), vec!["input"]);
+    }
+
+    #[test]
+    fn test_read_example() {
+        assert_eq!(read(10, "small").unwrap(), "0123\n1234\n8765\n9876\n");
+        assert!(read(10, "missing").is_err());
+    }
+
+    #[test]
+    fn test_params() {
+        let example = params(14, "example").unwrap();
+        assert_eq!(example.get("width").map(String::as_str), Some("11"));
+        assert!(params(14, "input").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_missing_day_has_no_inputs() {
+        assert!(names(99).is_empty());
+        assert!(read(99, "input").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::aoc::{examples, inputs};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Known answers for the inputs of a single day, read from `input/dayN/answers.txt` and
+/// from the answers given with the day's examples.
+///
+/// Every non-empty line of `answers.txt` has the form `<input>.part<N> = <answer>`, where
+/// `<input>` is the input file name without the `.txt` extension. Lines starting with `#`
+/// are comments.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    answers: BTreeMap<(String, u8), String>,
+}
+
+impl Manifest {
+    pub fn path(day: u8) -> PathBuf {
+        inputs::day_dir(day).join("answers.txt")
+    }
+
+    /// Loads the manifest of a day; a day without an answers file only has the answers of
+    /// its examples.
+    pub fn load(day: u8) -> Result<Manifest, String> {
+        let path = Manifest::path(day);
+        let mut manifest = if path.exists() {
+            let text = fs::read_to_string(&path).map_err(|err| {
+                format!("failed to read answers file {}: {}", path.display(), err)
+            })?;
+            Manifest::parse(&text).map_err(|err| format!("{}: {}", path.display(), err))?
+        } else {
+            Manifest::default()
+        };
+
+        for example in examples::load(day)? {
+            for (part, answer) in example.answers {
+                manifest
+                    .answers
+                    .insert((example.name.clone(), part), answer);
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    pub fn parse(text: &str) -> Result<Manifest, String> {
+        let mut answers = BTreeMap::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || format!("line {}: expected `<input>.part<N> = <answer>`", index + 1);
+            let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+            let (input, part) = key.trim().rsplit_once(".part").ok_or_else(invalid)?;
+            let part = part.parse::<u8>().map_err(|_| invalid())?;
+
+            answers.insert((input.to_string(), part), value.trim().to_string());
+        }
+
+        Ok(Manifest { answers })
+    }
+
+    pub fn expected(&self, input: &str, part: u8) -> Option<&str> {
+        self.answers
+            .get(&(input.to_string(), part))
+            .map(String::as_str)
+    }
+
+    /// Names of the input files that have at least one known answer.
+    pub fn inputs(&self) -> Vec<&str> {
+        let mut inputs: Vec<&str> = self
+            .answers
+            .keys()
+            .map(|(input, _)| input.as_str())
+            .collect();
+        inputs.dedup();
+        inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let manifest =
+            Manifest::parse("# comment\nexample.part1 = 11\n\ninput.part2 = 6,1\n").unwrap();
+
+        assert_eq!(manifest.expected("example", 1), Some("11"));
+        assert_eq!(manifest.expected("input", 2), Some("6,1"));
+        assert_eq!(manifest.expected("input", 1), None);
+        assert_eq!(manifest.inputs(), vec!["example", "input"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        assert!(Manifest::parse("example.part1 11").is_err());
+        assert!(Manifest::parse("example = 11").is_err());
+        assert!(Manifest::parse("example.partx = 11").is_err());
+    }
+
+    #[test]
+    fn test_every_day_has_a_manifest() {
+        for day in 1..=25 {
+            let manifest = Manifest::load(day).unwrap();
+            assert!(manifest.expected("input", 1).is_some(), "day {}", day);
+        }
+    }
+
+    #[test]
+    fn test_load_includes_example_answers() {
+        let manifest = Manifest::load(12).unwrap();
+        assert_eq!(manifest.expected("example", 1), Some("1930"));
+        assert_eq!(manifest.expected("small", 2), Some("80"));
+        assert_eq!(manifest.expected("input", 2), Some("966476"));
+    }
+}
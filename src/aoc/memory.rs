@@ -0,0 +1,189 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Allocations made by the current thread while a [`measure`] call was running.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, counting every reallocation as one.
+    pub allocations: u64,
+    /// Total bytes requested, including the new size of every reallocation.
+    pub bytes: u64,
+    /// Highest number of bytes allocated during the call and not yet freed.
+    pub peak: u64,
+}
+
+/// Global allocator that forwards to the system allocator and counts the allocations of a
+/// thread while it runs [`measure`]. Outside of `measure` it only checks a thread-local flag.
+pub struct CountingAllocator;
+
+#[derive(Clone, Copy)]
+struct Counters {
+    enabled: bool,
+    allocations: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+impl Counters {
+    const IDLE: Counters = Counters {
+        enabled: false,
+        allocations: 0,
+        bytes: 0,
+        live: 0,
+        peak: 0,
+    };
+
+    fn allocate(&mut self, size: usize) {
+        self.allocations += 1;
+        self.bytes += size as u64;
+        self.grow(size as i64);
+    }
+
+    fn grow(&mut self, delta: i64) {
+        self.live += delta;
+        self.peak = self.peak.max(self.live);
+    }
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const { Cell::new(Counters::IDLE) };
+}
+
+/// Updates the counters of the current thread if it is being measured. `try_with` keeps
+/// allocations made while the thread is torn down from panicking.
+fn record(update: impl FnOnce(&mut Counters)) {
+    let _ = COUNTERS.try_with(|cell| {
+        let mut counters = cell.get();
+        if counters.enabled {
+            update(&mut counters);
+            cell.set(counters);
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(|counters| counters.allocate(layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(|counters| counters.allocate(layout.size()));
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(|counters| counters.grow(-(layout.size() as i64)));
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(|counters| {
+            counters.allocations += 1;
+            counters.bytes += new_size as u64;
+            counters.grow(new_size as i64 - layout.size() as i64);
+        });
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Runs `f` and counts the allocations it makes on the current thread. Memory freed during
+/// the call but allocated before it does not lower the peak below zero. Nested calls are
+/// also counted by the enclosing one.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let outer = COUNTERS.with(|cell| {
+        cell.replace(Counters {
+            enabled: true,
+            ..Counters::IDLE
+        })
+    });
+    let result = f();
+    let inner = COUNTERS.with(|cell| cell.get());
+
+    let mut restored = outer;
+    if outer.enabled {
+        restored.allocations += inner.allocations;
+        restored.bytes += inner.bytes;
+        restored.peak = restored.peak.max(outer.live + inner.peak);
+        restored.live += inner.live;
+    }
+    COUNTERS.with(|cell| cell.set(restored));
+
+    let stats = AllocStats {
+        allocations: inner.allocations,
+        bytes: inner.bytes,
+        peak: inner.peak.max(0) as u64,
+    };
+    (result, stats)
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    #[test]
+    fn test_measure_counts_allocations() {
+        let (vec, stats) = measure(|| black_box(vec![0u8; 1000]));
+
+        assert_eq!(vec.len(), 1000);
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocations: 1,
+                bytes: 1000,
+                peak: 1000
+            }
+        );
+    }
+
+    #[test]
+    fn test_peak_is_the_most_live_at_once() {
+        let ((), stats) = measure(|| {
+            for _ in 0..10 {
+                drop(black_box(Vec::<u8>::with_capacity(100)));
+            }
+        });
+
+        assert_eq!(stats.allocations, 10);
+        assert_eq!(stats.bytes, 1000);
+        assert_eq!(stats.peak, 100);
+    }
+
+    #[test]
+    fn test_nested_measurements_add_up() {
+        let ((), outer) = measure(|| {
+            let first = black_box(vec![0u8; 64]);
+            let (_, inner) = measure(|| black_box(vec![0u8; 32]));
+            assert_eq!(inner.bytes, 32);
+            drop(first);
+        });
+
+        assert_eq!(outer.allocations, 2);
+        assert_eq!(outer.bytes, 96);
+        assert_eq!(outer.peak, 96);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(2048), "2.0KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 + 512 * 1024), "3.5MiB");
+    }
+}
@@ -0,0 +1,11 @@
+pub(crate) mod answer;
+pub(crate) mod examples;
+pub(crate) mod inputs;
+pub(crate) mod manifest;
+pub(crate) mod memory;
+pub(crate) mod params;
+pub(crate) mod parse;
+pub(crate) mod registry;
+pub(crate) mod solution;
+#[cfg(test)]
+pub(crate) mod testing;
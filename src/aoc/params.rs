@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Puzzle constants of a day that differ between the examples and the real input, such as
+/// a grid size or a number of steps. The defaults are the values of the real puzzle.
+pub trait Params: Default {
+    /// Overrides the constant called `key`; unknown keys and malformed values are errors.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+
+    /// Rejects values the day can't be solved with, e.g. a grid without cells.
+    fn check(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// The defaults with every override applied, e.g. the `key=value` pairs of an example.
+    fn with_overrides(overrides: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut params = Self::default();
+        for (key, value) in overrides {
+            params.set(key, value)?;
+        }
+        params.check()?;
+        Ok(params)
+    }
+}
+
+/// A day without parameters rejects every override.
+impl Params for () {
+    fn set(&mut self, key: &str, _value: &str) -> Result<(), String> {
+        Err(unknown(key, &[]))
+    }
+}
+
+/// Declares a parameters struct with a default for every field and implements [`Params`]
+/// for it, so each field can be overridden by its name. An optional `check` after the
+/// struct becomes [`Params::check`]:
+///
+/// ```ignore
+/// params! {
+///     pub struct Params {
+///         width: i32 = 101,
+///         height: i32 = 103,
+///     }
+///
+///     fn check(&self) -> Result<(), String> {
+///         positive(&[("width", self.width), ("height", self.height)])
+///     }
+/// }
+/// ```
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $($(#[$field_meta:meta])* $field:ident: $type:ty = $default:expr),* $(,)?
+        }
+
+        $(fn check(&$this:ident) -> Result<(), String> $check:block)?
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name {
+            $($(#[$field_meta])* pub $field: $type),*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($field: $default),*
+                }
+            }
+        }
+
+        impl $crate::aoc::params::Params for $name {
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(stringify!($field) => self.$field = $crate::aoc::params::value(key, value)?,)*
+                    _ => return Err($crate::aoc::params::unknown(key, &[$(stringify!($field)),*])),
+                }
+                Ok(())
+            }
+
+            $(fn check(&$this) -> Result<(), String> $check)?
+        }
+    };
+}
+
+pub(crate) use params;
+
+pub fn value<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for parameter {}: {}", key, value))
+}
+
+pub fn unknown(key: &str, known: &[&str]) -> String {
+    if known.is_empty() {
+        format!("unknown parameter {}: this day takes none", key)
+    } else {
+        format!(
+            "unknown parameter {} (expected one of {})",
+            key,
+            known.join(", ")
+        )
+    }
+}
+
+/// Rejects the first of `values` that is zero or negative, by the name it is given.
+pub fn positive<T: Default + PartialOrd + Display>(values: &[(&str, T)]) -> Result<(), String> {
+    match values.iter().find(|(_, value)| *value <= T::default()) {
+        Some((key, value)) => Err(format!("parameter {} must be positive, not {}", key, value)),
+        None => Ok(()),
+    }
+}
+
+/// Rejects the first of `values` that is negative, by the name it is given.
+pub fn non_negative<T: Default + PartialOrd + Display>(values: &[(&str, T)]) -> Result<(), String> {
+    match values.iter().find(|(_, value)| *value < T::default()) {
+        Some((key, value)) => Err(format!(
+            "parameter {} must be zero or more, not {}",
+            key, value
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Parses `key=value` command-line overrides.
+pub fn parse_override(text: &str) -> Result<(String, String), String> {
+    match text.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected a parameter as key=value, found {}", text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        /// Parameters of a grid puzzle.
+        pub struct Grid {
+            width: i32 = 101,
+            /// Rows of the grid.
+            height: i32 = 103,
+        }
+
+        fn check(&self) -> Result<(), String> {
+            positive(&[("width", self.width), ("height", self.height)])
+        }
+    }
+
+    fn overrides(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|&(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_defaults_and_overrides() {
+        assert_eq!(
+            Grid::default(),
+            Grid {
+                width: 101,
+                height: 103
+            }
+        );
+
+        let grid = Grid::with_overrides(&overrides(&[("width", "11"), ("height", "7")])).unwrap();
+        assert_eq!((grid.width, grid.height), (11, 7));
+    }
+
+    #[test]
+    fn test_rejects_unknown_and_malformed_overrides() {
+        assert_eq!(
+            Grid::with_overrides(&overrides(&[("depth", "3")])).unwrap_err(),
+            "unknown parameter depth (expected one of width, height)"
+        );
+        assert!(Grid::with_overrides(&overrides(&[("width", "wide")])).is_err());
+        assert!(<()>::with_overrides(&overrides(&[("width", "11")])).is_err());
+        assert!(<()>::with_overrides(&BTreeMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_check_rejects_out_of_range_values() {
+        assert_eq!(
+            Grid::with_overrides(&overrides(&[("height", "0")])).unwrap_err(),
+            "parameter height must be positive, not 0"
+        );
+        assert!(Grid::with_overrides(&overrides(&[("width", "-3")])).is_err());
+        assert!(positive(&[("size", 1usize)]).is_ok());
+        assert!(non_negative(&[("steps", 0)]).is_ok());
+        assert!(non_negative(&[("steps", -1)]).is_err());
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(
+            parse_override("floor=64"),
+            Ok(("floor".to_string(), "64".to_string()))
+        );
+        assert!(parse_override("floor").is_err());
+        assert!(parse_override("=64").is_err());
+    }
+}
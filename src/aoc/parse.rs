@@ -0,0 +1,161 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A malformed puzzle input: where parsing stopped and what the parser expected there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Day whose parser failed, or 0 when the error did not come from a registered day.
+    pub day: u8,
+    /// 1-based line of the input.
+    pub line: usize,
+    /// 1-based column of the line, counted in characters.
+    pub column: usize,
+    /// The token the parser was looking for, e.g. "a number" or "`|`".
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            day: 0,
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// An error at the start of `token`, which must be a slice of `input`; its line and
+    /// column are recovered from where it sits in `input`. An empty slice at the end of a
+    /// line points just past the line.
+    pub fn at(input: &str, token: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len() && input.is_char_boundary(offset))
+            .unwrap_or(input.len());
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        ParseError::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            expected,
+        )
+    }
+
+    pub fn with_day(self, day: u8) -> ParseError {
+        ParseError { day, ..self }
+    }
+
+    /// The error followed by the offending line of `input` with a caret under the column.
+    pub fn render(&self, input: &str) -> String {
+        let text = input.lines().nth(self.line.saturating_sub(1)).unwrap_or("");
+        let number = self.line.to_string();
+        let margin = " ".repeat(number.len());
+
+        format!(
+            "{}\n{} |\n{} | {}\n{} | {}^",
+            self,
+            margin,
+            number,
+            text,
+            margin,
+            " ".repeat(self.column.saturating_sub(1))
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.day > 0 {
+            write!(f, "day {}: ", self.day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, "a number"))
+}
+
+/// Splits `text`, a slice of `input`, around the first `separator`; a missing separator
+/// is reported at the end of `text`.
+pub fn split_once<'a>(
+    input: &str,
+    text: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(separator)
+        .ok_or_else(|| ParseError::at(input, &text[text.len()..], format!("`{}`", separator)))
+}
+
+/// Checks that a marker such as a start tile appears somewhere in `input`; a missing one is
+/// reported at the end of the input.
+pub fn require(input: &str, marker: char) -> Result<(), ParseError> {
+    if input.contains(marker) {
+        Ok(())
+    } else {
+        Err(ParseError::at(
+            input,
+            &input[input.len()..],
+            format!("`{}`", marker),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "47|53\n97|1x\n";
+
+    #[test]
+    fn test_at_finds_line_and_column() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let error = ParseError::at(INPUT, &line[3..], "a number");
+
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.to_string(), "line 2, column 4: expected a number");
+    }
+
+    #[test]
+    fn test_render() {
+        let error = ParseError::new(2, 4, "a number").with_day(5);
+        assert_eq!(
+            error.render(INPUT),
+            "day 5: line 2, column 4: expected a number\n  |\n2 | 97|1x\n  |    ^"
+        );
+    }
+
+    #[test]
+    fn test_number() {
+        let line = INPUT.lines().nth(1).unwrap();
+        assert_eq!(number::<i32>(INPUT, &line[..2]), Ok(97));
+        assert_eq!(number::<i32>(INPUT, &line[3..]).unwrap_err().column, 4);
+    }
+
+    #[test]
+    fn test_split_once() {
+        let line = INPUT.lines().next().unwrap();
+        assert_eq!(split_once(INPUT, line, "|"), Ok(("47", "53")));
+
+        let error = split_once(INPUT, line, ",").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+        assert_eq!(error.expected, "`,`");
+    }
+
+    #[test]
+    fn test_require() {
+        assert_eq!(require(INPUT, '|'), Ok(()));
+        assert_eq!(
+            require(INPUT, '^').unwrap_err().to_string(),
+            "line 3, column 1: expected `^`"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use crate::aoc::solution::DynSolution;
+use crate::*;
+
+/// Every day of the calendar, in order.
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &day1::solution::Day1,
+    &day2::solution::Day2,
+    &day3::solution::Day3,
+    &day4::solution::Day4,
+    &day5::solution::Day5,
+    &day6::solution::Day6,
+    &day7::solution::Day7,
+    &day8::solution::Day8,
+    &day9::solution::Day9,
+    &day10::solution::Day10,
+    &day11::solution::Day11,
+    &day12::solution::Day12,
+    &day13::solution::Day13,
+    &day14::solution::Day14,
+    &day15::solution::Day15,
+    &day16::solution::Day16,
+    &day17::solution::Day17,
+    &day18::solution::Day18,
+    &day19::solution::Day19,
+    &day20::solution::Day20,
+    &day21::solution::Day21,
+    &day22::solution::Day22,
+    &day23::solution::Day23,
+    &day24::solution::Day24,
+    &day25::solution::Day25,
+];
+
+pub fn get(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_ordered_by_day() {
+        for (index, solution) in SOLUTIONS.iter().enumerate() {
+            assert_eq!(solution.day() as usize, index + 1);
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get(7).map(|solution| solution.day()), Some(7));
+        assert!(get(0).is_none());
+        assert!(get(26).is_none());
+    }
+
+    #[test]
+    fn test_last_day_has_a_single_part() {
+        assert_eq!(get(25).unwrap().parts(), 1);
+        assert_eq!(get(24).unwrap().parts(), 2);
+    }
+}
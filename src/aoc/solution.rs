@@ -0,0 +1,139 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::params::Params;
+use crate::aoc::parse::ParseError;
+use std::any::Any;
+use std::collections::BTreeMap;
+
+/// Two ways of working out the same answer: a `reference` simple enough to trust, usually a
+/// brute force, and the `optimised` way the day really answers. They must agree on every
+/// input, which the differential tests check on generated ones.
+pub struct Pair<I, P> {
+    pub name: &'static str,
+    pub reference: fn(&I, &P) -> Answer,
+    pub optimised: fn(&I, &P) -> Answer,
+}
+
+/// Either side of a [`Pair`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Reference,
+    Optimised,
+}
+
+/// A single day of the puzzle: how to parse its input and how to solve both parts.
+pub trait Solution {
+    type Input;
+    /// Constants of the puzzle that examples may override, or `()` for none.
+    type Params: Params;
+
+    const DAY: u8;
+    const PARTS: u8 = 2;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Answer;
+
+    fn part2(_input: &Self::Input, _params: &Self::Params) -> Answer {
+        Answer::Empty
+    }
+
+    /// Answers worked out in two ways, to test one against the other.
+    fn pairs() -> Vec<Pair<Self::Input, Self::Params>> {
+        Vec::new()
+    }
+}
+
+/// Object-safe view of a [`Solution`], so days with different input types
+/// can live side by side in the registry.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+
+    fn parts(&self) -> u8;
+
+    /// Parses an input; errors carry the day they come from.
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    /// The parameters of the day with `overrides` applied to its defaults.
+    fn params(&self, overrides: &BTreeMap<String, String>) -> Result<Box<dyn Any>, String>;
+
+    fn solve(&self, parsed: &dyn Any, params: &dyn Any, part: u8) -> Answer;
+
+    /// Names of the pairs of [`Solution::pairs`], in order.
+    fn pair_names(&self) -> Vec<&'static str>;
+
+    /// Answers with one side of the pair at `index`.
+    fn solve_pair(&self, parsed: &dyn Any, params: &dyn Any, index: usize, side: Side) -> Answer;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+    S::Params: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parts(&self) -> u8 {
+        S::PARTS
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        match S::parse(input) {
+            Ok(parsed) => Ok(Box::new(parsed)),
+            Err(err) => Err(err.with_day(S::DAY)),
+        }
+    }
+
+    fn params(&self, overrides: &BTreeMap<String, String>) -> Result<Box<dyn Any>, String> {
+        match S::Params::with_overrides(overrides) {
+            Ok(params) => Ok(Box::new(params)),
+            Err(err) => Err(format!("day {}: {}", S::DAY, err)),
+        }
+    }
+
+    fn solve(&self, parsed: &dyn Any, params: &dyn Any, part: u8) -> Answer {
+        let (input, params) = downcast::<S>(parsed, params);
+
+        match part {
+            1 => S::part1(input, params),
+            2 if S::PARTS >= 2 => S::part2(input, params),
+            _ => panic!("day {} has no part {}", S::DAY, part),
+        }
+    }
+
+    fn pair_names(&self) -> Vec<&'static str> {
+        S::pairs().iter().map(|pair| pair.name).collect()
+    }
+
+    fn solve_pair(&self, parsed: &dyn Any, params: &dyn Any, index: usize, side: Side) -> Answer {
+        let (input, params) = downcast::<S>(parsed, params);
+        let pairs = S::pairs();
+        let pair = pairs
+            .get(index)
+            .unwrap_or_else(|| panic!("day {} has no pair {}", S::DAY, index));
+
+        match side {
+            Side::Reference => (pair.reference)(input, params),
+            Side::Optimised => (pair.optimised)(input, params),
+        }
+    }
+}
+
+fn downcast<'a, S: Solution>(
+    parsed: &'a dyn Any,
+    params: &'a dyn Any,
+) -> (&'a S::Input, &'a S::Params)
+where
+    S::Input: 'static,
+    S::Params: 'static,
+{
+    let input = parsed
+        .downcast_ref::<S::Input>()
+        .unwrap_or_else(|| panic!("parsed input does not belong to day {}", S::DAY));
+    let params = params
+        .downcast_ref::<S::Params>()
+        .unwrap_or_else(|| panic!("parameters do not belong to day {}", S::DAY));
+    (input, params)
+}
@@ -0,0 +1,87 @@
+use crate::aoc::examples;
+use crate::aoc::inputs;
+use crate::aoc::manifest::Manifest;
+use crate::aoc::solution::DynSolution;
+use std::collections::BTreeMap;
+
+/// Generates `test_known_answers_part1` and `test_known_answers_part2` for a day, checking
+/// every example with a known answer and every personal input listed in `answers.txt`.
+macro_rules! solution_tests {
+    ($solution:expr) => {
+        #[test]
+        fn test_known_answers_part1() {
+            $crate::aoc::testing::check_part(&$solution, 1);
+        }
+
+        #[test]
+        fn test_known_answers_part2() {
+            $crate::aoc::testing::check_part(&$solution, 2);
+        }
+    };
+}
+
+pub(crate) use solution_tests;
+
+/// Solves `part` on every input of the day with a known answer, examples with the
+/// parameters they override, and panics with every mismatch. A part the day does not have
+/// passes trivially.
+pub fn check_part(solution: &dyn DynSolution, part: u8) {
+    if part > solution.parts() {
+        return;
+    }
+
+    let day = solution.day();
+    let mut cases = Vec::new();
+
+    for example in examples::load(day).unwrap_or_else(|err| panic!("{}", err)) {
+        if let Some(expected) = example.answers.get(&part) {
+            let params = solution
+                .params(&example.params)
+                .unwrap_or_else(|err| panic!("example {}: {}", example.name, err));
+            cases.push((
+                example.name.clone(),
+                example.input.clone(),
+                params,
+                expected.clone(),
+            ));
+        }
+    }
+
+    let manifest = Manifest::load(day).unwrap_or_else(|err| panic!("{}", err));
+    for name in inputs::personal(day) {
+        if let Some(expected) = manifest.expected(&name, part) {
+            let input = inputs::read(day, &name).unwrap_or_else(|err| panic!("{}", err));
+            let params = solution
+                .params(&BTreeMap::new())
+                .unwrap_or_else(|err| panic!("{}", err));
+            cases.push((name, input, params, expected.to_string()));
+        }
+    }
+
+    assert!(
+        !cases.is_empty(),
+        "day {} part {} has no example or input with a known answer",
+        day,
+        part
+    );
+
+    let failures: Vec<String> = cases
+        .iter()
+        .filter_map(|(name, input, params, expected)| {
+            let answer = match solution.parse(input) {
+                Ok(parsed) => solution
+                    .solve(parsed.as_ref(), params.as_ref(), part)
+                    .to_string(),
+                Err(err) => return Some(format!("{}:\n{}", name, err.render(input))),
+            };
+            (answer != *expected).then(|| {
+                format!(
+                    "day {} part {} on {}: expected {}, got {}",
+                    day, part, name, expected, answer
+                )
+            })
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
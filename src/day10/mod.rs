@@ -1 +1 @@
-mod solution;
+pub(crate) mod solution;
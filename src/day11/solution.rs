@@ -1,91 +1,90 @@
-use std::collections::HashMap;
+use crate::aoc::answer::Answer;
+use crate::aoc::params::{non_negative, params};
+use crate::aoc::parse::{number, ParseError};
+use crate::aoc::solution::Solution;
+use crate::utils::memo::Memo;
 
-pub fn part1(input: &str) -> i64 {
-    let mut memo: HashMap<(i64, i32), i64> = HashMap::new();
-    parse(input)
-        .into_iter()
-        .map(|stone| count_stones(stone, 25, &mut memo))
-        .sum()
+params! {
+    pub struct Params {
+        blinks_part1: i32 = 25,
+        blinks_part2: i32 = 75,
+    }
+
+    fn check(&self) -> Result<(), String> {
+        non_negative(&[("blinks_part1", self.blinks_part1), ("blinks_part2", self.blinks_part2)])
+    }
 }
 
-pub fn part2(input: &str) -> i64 {
-    let mut memo: HashMap<(i64, i32), i64> = HashMap::new();
-    parse(input)
-        .into_iter()
-        .map(|stone| count_stones(stone, 75, &mut memo))
+/// Number of stones after blinking `blinks` times.
+pub fn blink(stones: &[i64], blinks: i32) -> i64 {
+    let mut memo = Memo::new();
+    stones
+        .iter()
+        .map(|&stone| count_stones(stone, blinks, &mut memo))
         .sum()
 }
 
-fn parse(input: &str) -> Vec<i64> {
-    input
-        .split_whitespace()
-        .map(str::trim)
-        .filter_map(|s| s.parse::<i64>().ok())
-        .collect()
+pub fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    input.split_whitespace().map(|s| number(input, s)).collect()
 }
 
-fn count_stones(stone: i64, blinks_left: i32, memo: &mut HashMap<(i64, i32), i64>) -> i64 {
+fn count_stones(stone: i64, blinks_left: i32, memo: &mut Memo<(i64, i32), i64>) -> i64 {
     if blinks_left == 0 {
         return 1;
     }
-    let key = (stone, blinks_left);
-    if let Some(&result) = memo.get(&key) {
-        return result;
-    }
-    let n = (stone.abs() as f64).log10().floor() as i64 + 1;
-    let result = if stone != 0 {
-        if n % 2 == 0 {
-            let pow = 10_i64.pow((n / 2) as u32);
-            count_stones(stone / pow, blinks_left - 1, memo)
-                + count_stones(stone % pow, blinks_left - 1, memo)
+
+    memo.get_or_insert_with((stone, blinks_left), |memo| {
+        let n = (stone.abs() as f64).log10().floor() as i64 + 1;
+        if stone != 0 {
+            if n % 2 == 0 {
+                let pow = 10_i64.pow((n / 2) as u32);
+                count_stones(stone / pow, blinks_left - 1, memo)
+                    + count_stones(stone % pow, blinks_left - 1, memo)
+            } else {
+                count_stones(stone * 2024, blinks_left - 1, memo)
+            }
         } else {
-            count_stones(stone * 2024, blinks_left - 1, memo)
+            count_stones(1, blinks_left - 1, memo)
         }
-    } else {
-        count_stones(1, blinks_left - 1, memo)
-    };
-    memo.insert(key, result);
-    result
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day11;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day11 {
+    type Input = Vec<i64>;
+    type Params = Params;
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day11/example.txt");
+    const DAY: u8 = 11;
 
-        let result = part1(&input);
-        assert_eq!(result, 189541, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day11/input.txt");
+    fn part1(input: &Self::Input, params: &Self::Params) -> Answer {
+        blink(input, params.blinks_part1).into()
+    }
 
-        let result = part1(&input);
-        assert_eq!(result, 184927, "Failed on my input case for part1");
+    fn part2(input: &Self::Input, params: &Self::Params) -> Answer {
+        blink(input, params.blinks_part2).into()
     }
+}
 
-    #[test]
-    fn test_example_case_part2() {
-        let input = load_input("input/day11/example.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::params::Params as _;
+    use crate::aoc::testing::solution_tests;
+    use std::collections::BTreeMap;
 
-        let result = part2(&input);
-        assert_eq!(result, 226596360258785, "Failed on my input case for part1");
-    }
+    solution_tests!(Day11);
 
     #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day11/input.txt");
-
-        let result = part2(&input);
-        assert_eq!(result, 220357186726677, "Failed on my input case for part1");
+    fn test_rejects_negative_blinks() {
+        let overrides =
+            |key: &str, value: &str| BTreeMap::from([(key.to_string(), value.to_string())]);
+        assert!(Params::with_overrides(&overrides("blinks_part1", "-1")).is_err());
+        assert!(Params::with_overrides(&overrides("blinks_part2", "-1")).is_err());
+        assert!(Params::with_overrides(&overrides("blinks_part1", "0")).is_ok());
     }
 }
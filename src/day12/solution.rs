@@ -1,131 +1,107 @@
-use crate::utils::dsu::DSU;
-use crate::utils::strings::split_element_by_element_array_input_into_vector;
-use std::collections::{HashMap, HashSet, VecDeque};
-
-fn flood_fill(
-    grid: &Vec<Vec<char>>,
-    x: usize,
-    y: usize,
-    visited: &mut HashSet<(usize, usize)>,
-) -> (usize, usize) {
-    let rows = grid.len();
-    let cols = grid[0].len();
-    let plant_type = grid[x][y];
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::Solution;
+use crate::utils::bitset::BitSet;
+use crate::utils::dsu::Dsu;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+use crate::utils::hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+
+fn flood_fill(grid: &Grid<char>, start: Point<usize>, visited: &mut BitSet) -> (usize, usize) {
+    let plant_type = grid[start];
     let mut stack = VecDeque::new();
     let mut area = 0;
     let mut perimeter = 0;
 
-    stack.push_back((x, y));
+    stack.push_back(start);
 
-    while let Some((cx, cy)) = stack.pop_front() {
-        if visited.contains(&(cx, cy)) {
+    while let Some(current) = stack.pop_front() {
+        if !visited.insert(grid.index_of(current).unwrap()) {
             continue;
         }
 
-        visited.insert((cx, cy));
         area += 1;
 
-        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-            let nx = cx as isize + dx;
-            let ny = cy as isize + dy;
-
-            if nx >= 0 && nx < rows as isize && ny >= 0 && ny < cols as isize {
-                let nx = nx as usize;
-                let ny = ny as usize;
-                if grid[nx][ny] == plant_type && !visited.contains(&(nx, ny)) {
-                    stack.push_back((nx, ny));
-                } else if grid[nx][ny] != plant_type {
-                    perimeter += 1;
+        // Every side not shared with a plot of the same type, including the grid's edge,
+        // contributes to the perimeter
+        let mut same_type = 0;
+        for next in grid.neighbours4(current) {
+            if grid[next] == plant_type {
+                same_type += 1;
+                if !visited.contains(grid.index_of(next).unwrap()) {
+                    stack.push_back(next);
                 }
-            } else {
-                perimeter += 1; // Out of bounds contributes to the perimeter
             }
         }
+        perimeter += 4 - same_type;
     }
 
     (area, perimeter)
 }
 
-pub fn part1(input_map: &str) -> usize {
-    let grid = split_element_by_element_array_input_into_vector(input_map);
-    let mut visited = HashSet::new();
+pub fn parse_input(input_map: &str) -> Result<Grid<char>, ParseError> {
+    Grid::chars(input_map)
+}
+
+pub fn part1(grid: &Grid<char>) -> usize {
+    let mut visited = BitSet::new(grid.width() * grid.height());
     let mut total_cost = 0;
 
-    for x in 0..grid.len() {
-        for y in 0..grid[0].len() {
-            if !visited.contains(&(x, y)) {
-                let (area, perimeter) = flood_fill(&grid, x, y, &mut visited);
-                total_cost += area * perimeter;
-            }
+    for position in grid.positions() {
+        if !visited.contains(grid.index_of(position).unwrap()) {
+            let (area, perimeter) = flood_fill(grid, position, &mut visited);
+            total_cost += area * perimeter;
         }
     }
 
     total_cost
 }
 
-pub fn part2(input_map: &str) -> i32 {
-    let grid: Vec<Vec<char>> = split_element_by_element_array_input_into_vector(input_map);
-    let n = grid.len();
-    let mut uf = DSU::new(n * n);
-
-    let to_coord = |a: usize, b: usize| a * n + b;
-
-    let dirs = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-
-    for i in 0..n {
-        for j in 0..n {
-            for (dx, dy) in dirs.iter() {
-                let newx = i as isize + dx;
-                let newy = j as isize + dy;
-                if newx >= 0
-                    && (newx as usize) < n
-                    && newy >= 0
-                    && (newy as usize) < n
-                    && grid[i][j] == grid[newx as usize][newy as usize]
-                {
-                    uf.merge(to_coord(i, j), to_coord(newx as usize, newy as usize));
-                }
-            }
-        }
-    }
+pub fn part2(grid: &Grid<char>) -> i32 {
+    let (rows, cols) = (grid.height(), grid.width());
+    let mut uf = Dsu::new(rows * cols);
 
-    let comps = uf.groups();
-    let mut comp_map = HashMap::new();
+    let to_coord = |position: Point<usize>| position.y * cols + position.x;
 
-    for x in &comps {
-        for &y in x {
-            comp_map.insert(y, x[0]);
+    for position in grid.positions() {
+        for next in grid.neighbours4(position) {
+            if grid[position] == grid[next] {
+                uf.merge(to_coord(position), to_coord(next));
+            }
         }
     }
 
-    let mut bnds = HashMap::new();
-    let mut extras = HashMap::new();
+    let mut bnds = FxHashMap::default();
+    let mut extras = FxHashMap::default();
 
-    for i in 0..=n {
-        for j in 0..=n {
-            let mut curcomps: HashMap<usize, HashSet<(isize, isize)>> = HashMap::new();
+    // Every lattice point between plots touches the four plots up and to its left
+    let around = [(0, 0), (-1, -1), (-1, 0), (0, -1)].map(|(dx, dy)| Point::new(dx, dy));
+    let diagonal = |a: usize, b: usize| FxHashSet::from_iter([around[a], around[b]]);
+
+    for y in 0..=rows {
+        for x in 0..=cols {
+            let mut curcomps: FxHashMap<usize, FxHashSet<Point<isize>>> = FxHashMap::default();
             let mut bad = false;
 
-            for &(dx, dy) in &[(0, 0), (-1, -1), (-1, 0), (0, -1)] {
-                let newx = i as isize + dx;
-                let newy = j as isize + dy;
-
-                if newx >= 0 && (newx as usize) < n && newy >= 0 && (newy as usize) < n {
-                    let coord = to_coord(newx as usize, newy as usize);
-                    curcomps
-                        .entry(*comp_map.get(&coord).unwrap())
-                        .or_insert_with(HashSet::new)
-                        .insert((dx, dy));
-                } else {
-                    bad = true;
+            for &delta in &around {
+                match Point::new(x, y)
+                    .offset(delta)
+                    .filter(|&p| grid.get(p).is_some())
+                {
+                    Some(plot) => {
+                        curcomps
+                            .entry(uf.find(to_coord(plot)))
+                            .or_default()
+                            .insert(delta);
+                    }
+                    None => bad = true,
                 }
             }
 
             if curcomps.len() != 1 || bad {
                 for (&c, s) in &curcomps {
-                    if s == &[(0, 0), (-1, -1)].iter().copied().collect()
-                        || s == &[(0, -1), (-1, 0)].iter().copied().collect()
-                    {
+                    if s == &diagonal(0, 1) || s == &diagonal(2, 3) {
                         *bnds.entry(c).or_insert(0) += 2;
                     } else {
                         *bnds.entry(c).or_insert(0) += 1;
@@ -140,51 +116,37 @@ pub fn part2(input_map: &str) -> i32 {
 
     let mut ans = 0;
     for (&c, &b) in &bnds {
-        let truth = comps.iter().find(|x| x.contains(&c)).unwrap().len();
-        ans += (b - extras.get(&c).unwrap_or(&0)) as i32 * truth as i32;
+        ans += (b - extras.get(&c).unwrap_or(&0)) * uf.size(c) as i32;
     }
 
     ans
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day12;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day12 {
+    type Input = Grid<char>;
+    type Params = ();
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day12/example.txt");
+    const DAY: u8 = 12;
 
-        let result = part1(&input);
-        assert_eq!(result, 1930, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day12/input.txt");
-
-        let result = part1(&input);
-        assert_eq!(result, 1573474, "Failed on my input case for part1");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
     }
 
-    #[test]
-    fn test_example_case_part2() {
-        let input = load_input("input/day12/example.txt");
-
-        let result = part2(&input);
-        assert_eq!(result, 1206, "Failed on my input case for part1");
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
 
-    #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day12/input.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
 
-        let result = part2(&input);
-        assert_eq!(result, 966476, "Failed on my input case for part1");
-    }
+    solution_tests!(Day12);
 }
@@ -1,143 +1,233 @@
-pub fn part1(input_map: &str) -> i32 {
-    let mut ans = 0;
-
-    let mut cura: Option<(i32, i32)> = None;
-    let mut curb: Option<(i32, i32)> = None;
-
-    let mut st = 0;
-    for line in input_map.lines() {
-        if line.trim().is_empty() {
-            st = 0;
-            continue;
-        }
-
-        if st == 0 {
-            let toks: Vec<&str> = line.split_whitespace().collect();
-            let x: i32 = toks[2][2..toks[2].len() - 1].parse().unwrap();
-            let y: i32 = toks[3][2..].parse().unwrap();
-            cura = Some((x, y));
-            st = 1;
-        } else if st == 1 {
-            let toks: Vec<&str> = line.split_whitespace().collect();
-            let x: i32 = toks[2][2..toks[2].len() - 1].parse().unwrap();
-            let y: i32 = toks[3][2..].parse().unwrap();
-            curb = Some((x, y));
-            st = 2;
-        } else {
-            let toks: Vec<&str> = line.split_whitespace().collect();
-            let x: i32 = toks[1][2..toks[1].len() - 1].parse().unwrap();
-            let y: i32 = toks[2][2..].parse().unwrap();
-
-            let mut score = 1_000_000;
-            for i in 0..=100 {
-                for j in 0..=100 {
-                    if let (Some(cura), Some(curb)) = (cura, curb) {
-                        if i * cura.0 + j * curb.0 == x && i * cura.1 + j * curb.1 == y {
-                            score = score.min(3 * i + j);
-                        }
-                    }
-                }
-            }
-
-            if score < 1_000_000 {
-                ans += score;
-            }
-            st = 0;
-        }
+use crate::aoc::answer::Answer;
+use crate::aoc::params::params;
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::{Pair, Solution};
+use crate::utils::num::{egcd, nonnegative_solutions};
+use crate::utils::strings::scan;
+
+params! {
+    pub struct Params {
+        /// Most presses of each button in part 1.
+        max_presses: i64 = 100,
+        /// Added to both coordinates of every prize in part 2.
+        offset: i64 = 10_000_000_000_000,
     }
-
-    ans
 }
 
-pub fn part2(input_map: &str) -> i64 {
-    let mut lines = input_map.lines();
+#[derive(Debug)]
+pub struct Machine {
+    a: (i64, i64),
+    b: (i64, i64),
+    prize: (i64, i64),
+}
 
-    let mut cura: Option<(i64, i64)> = None;
-    let mut curb: Option<(i64, i64)> = None;
-    let mut ans: i64 = 0;
-    let mut st = 0;
+pub fn parse_input(input_map: &str) -> Result<Vec<Machine>, ParseError> {
+    let mut machines = Vec::new();
 
+    let mut lines = input_map.lines().filter(|line| !line.trim().is_empty());
     while let Some(line) = lines.next() {
-        if line.trim().is_empty() {
-            st = 0;
-            continue;
-        }
-
-        let toks: Vec<&str> = line.split_whitespace().collect();
-        if st == 0 {
-            let x = toks[2][2..toks[2].len() - 1].parse::<i64>().unwrap();
-            let y = toks[3][2..].parse::<i64>().unwrap();
-            cura = Some((x, y));
-            st = 1;
-        } else if st == 1 {
-            let x = toks[2][2..toks[2].len() - 1].parse::<i64>().unwrap();
-            let y = toks[3][2..].parse::<i64>().unwrap();
-            curb = Some((x, y));
-            st = 2;
-        } else {
-            let x = toks[1][2..toks[1].len() - 1].parse::<i64>().unwrap() + 10_000_000_000_000;
-            let y = toks[2][2..].parse::<i64>().unwrap() + 10_000_000_000_000;
-
-            if let (Some((a, c)), Some((b, d))) = (cura, curb) {
-                let denom = a * d - b * c;
-                if denom != 0 {
-                    let i_num = d * x - b * y;
-                    let j_num = -c * x + a * y;
-
-                    if i_num % denom == 0 && j_num % denom == 0 {
-                        let i = i_num / denom;
-                        let j = j_num / denom;
-
-                        if i > 0 && j > 0 {
-                            ans += 3 * i + j;
-                        }
-                    }
-                }
-            }
-        }
+        let end = &input_map[input_map.len()..];
+        let a = parse_coordinates(input_map, line, "Button A: X+{}, Y+{}")?;
+        let b = parse_coordinates(
+            input_map,
+            lines.next().unwrap_or(end),
+            "Button B: X+{}, Y+{}",
+        )?;
+        let prize = parse_coordinates(input_map, lines.next().unwrap_or(end), "Prize: X={}, Y={}")?;
+        machines.push(Machine { a, b, prize });
     }
 
-    ans
+    Ok(machines)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+/// Parses a line matching `pattern`, whose two placeholders are the coordinates.
+fn parse_coordinates(input: &str, line: &str, pattern: &str) -> Result<(i64, i64), ParseError> {
+    let [x, y] = scan(input, line, pattern)?.numbers()?;
+    Ok((x, y))
+}
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
+/// Fewest tokens to win the prize: each A press costs 3 and each B press 1, and neither
+/// button may be pressed more than `max_presses` times. `None` if the prize can't be won.
+fn cheapest_win(machine: &Machine, offset: i64, max_presses: i64) -> Option<i64> {
+    let ((ax, ay), (bx, by)) = (machine.a, machine.b);
+    let prize = (machine.prize.0 + offset, machine.prize.1 + offset);
+    if ax * by == ay * bx {
+        return cheapest_win_parallel(machine, prize, max_presses);
     }
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day13/example.txt");
+    nonnegative_solutions(&[vec![ax, bx], vec![ay, by]], &[prize.0, prize.1])?
+        .into_iter()
+        .filter(|presses| presses.iter().all(|&n| n <= max_presses))
+        .map(|presses| 3 * presses[0] + presses[1])
+        .min()
+}
 
-        let result = part1(&input);
-        assert_eq!(result, 480, "Failed on example case for part1");
+/// Fewest tokens when both buttons move the claw along the same line, where any number of
+/// mixes of presses may reach the prize. Along one axis the presses solve `a*u + b*v = p`,
+/// whose solutions are `a = a0 + k*v/g` and `b = b0 - k*u/g` for `g = gcd(u, v)`; the cost
+/// changes linearly with `k`, so the cheapest win is at one end of the range of `k` that
+/// keeps both counts between 0 and `max_presses`.
+fn cheapest_win_parallel(machine: &Machine, prize: (i64, i64), max_presses: i64) -> Option<i64> {
+    let ((ax, ay), (bx, by)) = (machine.a, machine.b);
+    let (u, v, p) = if (ax, bx) != (0, 0) {
+        (ax, bx, prize.0)
+    } else {
+        (ay, by, prize.1)
+    };
+    if (u, v) == (0, 0) {
+        return (prize == (0, 0)).then_some(0);
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day13/input.txt");
+    let (g, x, y) = egcd(u, v);
+    if p % g != 0 {
+        return None;
+    }
+    let (u, v, p, g, max) = (
+        u as i128,
+        v as i128,
+        p as i128,
+        g as i128,
+        max_presses as i128,
+    );
+    let (a0, b0) = (x as i128 * (p / g), y as i128 * (p / g));
+    let (step_a, step_b) = (v / g, -(u / g));
+
+    let (low_a, high_a) = press_range(a0, step_a, max)?;
+    let (low_b, high_b) = press_range(b0, step_b, max)?;
+    let (low, high) = (low_a.max(low_b), high_a.min(high_b));
+    if low > high {
+        return None;
+    }
 
-        let result = part1(&input);
-        assert_eq!(result, 29877, "Failed on my input case for part1");
+    let k = if 3 * step_a + step_b >= 0 { low } else { high };
+    let (a, b) = (a0 + k * step_a, b0 + k * step_b);
+    let reaches = |(ax, ay): (i64, i64), (bx, by): (i64, i64)| {
+        (
+            a * ax as i128 + b * bx as i128,
+            a * ay as i128 + b * by as i128,
+        ) == (prize.0 as i128, prize.1 as i128)
+    };
+    // The other axis only agrees when the prize lies on the buttons' line
+    if !reaches(machine.a, machine.b) {
+        return None;
     }
+    i64::try_from(3 * a + b).ok()
+}
 
-    #[test]
-    fn test_example_case_part2() {
-        let input = load_input("input/day13/example.txt");
+/// The range of `k` for which `start + k * step` lies in `0..=max`, unbounded when `step`
+/// is 0 and `start` already does. `None` when no `k` works.
+fn press_range(start: i128, step: i128, max: i128) -> Option<(i128, i128)> {
+    let floor = |n: i128, d: i128| n.div_euclid(d);
+    let ceil = |n: i128, d: i128| -(-n).div_euclid(d);
+    match step.signum() {
+        0 => (0..=max).contains(&start).then_some((i128::MIN, i128::MAX)),
+        1 => Some((ceil(-start, step), floor(max - start, step))),
+        _ => Some((ceil(start - max, -step), floor(start, -step))),
+    }
+}
 
-        let result = part2(&input);
-        assert_eq!(result, 875318608908, "Failed on my input case for part1");
+pub fn part1(machines: &[Machine], max_presses: i64) -> i64 {
+    machines
+        .iter()
+        .filter_map(|machine| cheapest_win(machine, 0, max_presses))
+        .sum()
+}
+
+pub fn part2(machines: &[Machine], offset: i64) -> i64 {
+    machines
+        .iter()
+        .filter_map(|machine| cheapest_win(machine, offset, i64::MAX))
+        .sum()
+}
+
+/// Part 1 by trying every number of presses of both buttons, to check the linear algebra.
+fn brute_force_part1(machines: &[Machine], max_presses: i64) -> i64 {
+    let cheapest = |machine: &Machine| {
+        let ((ax, ay), (bx, by)) = (machine.a, machine.b);
+        (0..=max_presses)
+            .flat_map(|a| (0..=max_presses).map(move |b| (a, b)))
+            .filter(|&(a, b)| (a * ax + b * bx, a * ay + b * by) == machine.prize)
+            .map(|(a, b)| 3 * a + b)
+            .min()
+    };
+    machines.iter().filter_map(cheapest).sum()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Machine>;
+    type Params = Params;
+
+    const DAY: u8 = 13;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Answer {
+        part1(input, params.max_presses).into()
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Answer {
+        part2(input, params.offset).into()
     }
 
+    fn pairs() -> Vec<Pair<Self::Input, Self::Params>> {
+        vec![Pair {
+            name: "fewest tokens",
+            reference: |input, params| brute_force_part1(input, params.max_presses).into(),
+            optimised: Self::part1,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
+
+    solution_tests!(Day13);
+
     #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day13/input.txt");
+    fn test_parse_errors() {
+        let error = parse_input("Button A: X+94, Y+34\nButton B: X+22\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 15));
+        assert_eq!(error.expected, "`, Y+`");
+
+        let error = parse_input("Button A: X+94, Y+34\nButton B: X+22, Y+67\n").unwrap_err();
+        assert_eq!(error.expected, "`Prize: X=`");
+    }
 
-        let result = part2(&input);
-        assert_eq!(result, 99423413811305, "Failed on my input case for part1");
+    #[test]
+    fn test_parallel_buttons() {
+        let machine = |a, b, prize| Machine { a, b, prize };
+
+        let both = machine((3, 3), (7, 7), (10, 10));
+        assert_eq!(cheapest_win(&both, 0, 100), Some(4));
+        assert_eq!(
+            cheapest_win(&both, 10_000_000_000_000, i64::MAX),
+            Some(1428571428578)
+        );
+
+        // A covers ten times the ground of B for three times the cost
+        let long_a = machine((10, 20), (1, 2), (25, 50));
+        assert_eq!(cheapest_win(&long_a, 0, 100), Some(11));
+        assert_eq!(
+            cheapest_win(&machine((10, 20), (1, 2), (44, 88)), 0, 4),
+            Some(16)
+        );
+        assert_eq!(
+            cheapest_win(&machine((10, 20), (1, 2), (45, 90)), 0, 4),
+            None
+        );
+
+        // Off the buttons' line, between their steps, or along an axis only B moves on
+        assert_eq!(cheapest_win(&machine((1, 2), (2, 4), (3, 5)), 0, 100), None);
+        assert_eq!(cheapest_win(&machine((2, 2), (4, 4), (3, 3)), 0, 100), None);
+        assert_eq!(
+            cheapest_win(&machine((0, 0), (0, 5), (0, 15)), 0, 100),
+            Some(3)
+        );
     }
 }
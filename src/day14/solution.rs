@@ -1,53 +1,66 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::params::{params, positive};
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::{Pair, Solution};
+use crate::utils::geom::Point;
+use crate::utils::num::{crt, lcm};
+use crate::utils::strings::scan;
 use std::collections::HashMap;
 
-struct Robot {
-    x: i32,
-    y: i32,
-    dx: i32,
-    dy: i32,
+params! {
+    pub struct Params {
+        width: i32 = 101,
+        height: i32 = 103,
+        /// Seconds the robots move before the safety factor is taken in part 1.
+        seconds: i32 = 100,
+    }
+
+    fn check(&self) -> Result<(), String> {
+        positive(&[("width", self.width), ("height", self.height)])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Robot {
+    position: Point<i32>,
+    velocity: Point<i32>,
 }
 
 impl Robot {
-    fn move_robot(&mut self, width: i32, height: i32) {
-        self.x = (self.x + self.dx).rem_euclid(width);
-        self.y = (self.y + self.dy).rem_euclid(height);
+    fn move_robot_step(&self, steps: i32, width: i32, height: i32) -> Point<i32> {
+        wrap(self.position + self.velocity * steps, width, height)
     }
+}
 
-    fn move_robot_step(&self, steps: i32, width: i32, height: i32) -> (i32, i32) {
-        let nx = (self.x + steps * self.dx).rem_euclid(width);
-        let ny = (self.y + steps * self.dy).rem_euclid(height);
-        (nx, ny)
-    }
+/// The point inside the room, which wraps around at its edges.
+fn wrap(point: Point<i32>, width: i32, height: i32) -> Point<i32> {
+    Point::new(point.x.rem_euclid(width), point.y.rem_euclid(height))
+}
 
-    fn new(px: i32, py: i32, vx: i32, vy: i32) -> Self {
-        Self {
-            x: px,
-            y: py,
-            dx: vx,
-            dy: vy,
-        }
-    }
+pub fn parse_input(input_map: &str) -> Result<Vec<Robot>, ParseError> {
+    input_map
+        .lines()
+        .map(|line| parse_robot(input_map, line))
+        .collect()
 }
 
-pub fn part1(input_map: &str) -> i32 {
-    const W: i32 = 101;
-    const H: i32 = 103;
+pub fn part1(robots: &[Robot], params: &Params) -> i32 {
+    let (w, h) = (params.width, params.height);
 
-    let robots: Vec<Robot> = input_map.lines().map(|line| parse_input(line)).collect();
-    let mut ans = vec![0, 0, 0, 0];
+    let mut ans = [0, 0, 0, 0];
 
     for robot in robots {
-        let (nx, ny) = robot.move_robot_step(100, W, H);
+        let Point { x: nx, y: ny } = robot.move_robot_step(params.seconds, w, h);
 
-        if nx == W / 2 || ny == H / 2 {
+        if nx == w / 2 || ny == h / 2 {
             continue;
         }
 
-        if nx < W / 2 && ny < H / 2 {
+        if nx < w / 2 && ny < h / 2 {
             ans[0] += 1;
-        } else if nx > W / 2 && ny < H / 2 {
+        } else if nx > w / 2 && ny < h / 2 {
             ans[1] += 1;
-        } else if nx < W / 2 && ny > H / 2 {
+        } else if nx < w / 2 && ny > h / 2 {
             ans[2] += 1;
         } else {
             ans[3] += 1;
@@ -57,86 +70,133 @@ pub fn part1(input_map: &str) -> i32 {
     ans[0] * ans[1] * ans[2] * ans[3]
 }
 
-fn parse_input(line: &str) -> Robot {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    let pos: Vec<i32> = parts[0][2..]
-        .split(',')
-        .map(|s| s.parse().unwrap())
-        .collect();
-    let vel: Vec<i32> = parts[1][2..]
-        .split(',')
-        .map(|s| s.parse().unwrap())
-        .collect();
-
-    Robot::new(pos[0], pos[1], vel[0], vel[1])
+fn parse_robot(input: &str, line: &str) -> Result<Robot, ParseError> {
+    let [px, py, vx, vy] = scan(input, line, "p={},{} v={},{}")?.numbers()?;
+
+    Ok(Robot {
+        position: Point::new(px, py),
+        velocity: Point::new(vx, vy),
+    })
 }
 
-pub fn part2(input_map: &str) -> i32 {
-    let mut robots: Vec<Robot> = input_map.lines().map(|line| parse_input(line)).collect();
-    const WIDTH: i32 = 101;
-    const HEIGHT: i32 = 103;
-    const SEARCH_STRING: &str = "########";
+/// The first second at which the robots draw a tree. Columns repeat every `width` seconds
+/// and rows every `height`, and the tree is where the robots bunch up most along both, so
+/// the seconds at which the columns and the rows are least spread out are combined with
+/// the CRT. Should that guess hold no tree, every second up to the full period is tried;
+/// a room without a tree by then never gets one.
+pub fn part2(robots: &[Robot], params: &Params) -> Option<i32> {
+    let (width, height) = (params.width, params.height);
+    let period = lcm(width as i64, height as i64) as i32;
+
+    let spread = |axis: fn(Point<i32>) -> i32, seconds: i32| {
+        let values: Vec<i64> = robots
+            .iter()
+            .map(|robot| axis(robot.move_robot_step(seconds, width, height)) as i64)
+            .collect();
+        let mean = values.iter().sum::<i64>() / values.len().max(1) as i64;
+        values.iter().map(|v| (v - mean).pow(2)).sum::<i64>()
+    };
+    let column_second = (0..width).min_by_key(|&s| spread(|p| p.x, s))?;
+    let row_second = (0..height).min_by_key(|&s| spread(|p| p.y, s))?;
+
+    if let Some((second, _)) = crt(&[
+        (column_second as i64, width as i64),
+        (row_second as i64, height as i64),
+    ]) {
+        // Second 0 is the start, so a tree then first reappears a whole period later
+        let second = if second == 0 { period } else { second as i32 };
+        if draws_tree(robots, second, width, height) {
+            return Some(second);
+        }
+    }
 
-    let mut tick = 0;
+    (1..=period).find(|&second| draws_tree(robots, second, width, height))
+}
 
-    loop {
-        tick += 1;
+/// Whether some row has a run of robots long enough to be the tree's frame after `seconds`.
+fn draws_tree(robots: &[Robot], seconds: i32, width: i32, height: i32) -> bool {
+    // Half the frame: robots crowd its rows at other seconds too, and make shorter runs by chance
+    const SEARCH_STRING: &str = "################";
 
-        for robot in robots.iter_mut() {
-            robot.move_robot(WIDTH, HEIGHT);
-        }
+    let mut map: HashMap<i32, Vec<i32>> = HashMap::new();
+    for robot in robots {
+        let position = robot.move_robot_step(seconds, width, height);
+        map.entry(position.y).or_default().push(position.x);
+    }
 
-        let mut map: HashMap<i32, Vec<i32>> = HashMap::new();
-        for robot in robots.iter() {
-            map.entry(robot.y).or_insert_with(Vec::new).push(robot.x);
+    map.values().any(|x_positions| {
+        if x_positions.len() < SEARCH_STRING.len() {
+            return false;
         }
 
-        for (.., x_positions) in map.iter() {
-            if x_positions.len() < SEARCH_STRING.len() {
-                continue;
-            }
-
-            let mut row = vec!['.'; WIDTH as usize];
-            for &x in x_positions {
-                row[x as usize] = '#';
-            }
-            if row.iter().collect::<String>().contains(SEARCH_STRING) {
-                return tick;
-            }
+        let mut row = vec!['.'; width as usize];
+        for &x in x_positions {
+            row[x as usize] = '#';
         }
-    }
+        row.iter().collect::<String>().contains(SEARCH_STRING)
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day14;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day14 {
+    type Input = Vec<Robot>;
+    type Params = Params;
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day14/example.txt");
+    const DAY: u8 = 14;
 
-        let result = part1(&input);
-        assert_eq!(result, 21, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day14/input.txt");
+    fn part1(input: &Self::Input, params: &Self::Params) -> Answer {
+        part1(input, params).into()
+    }
 
-        let result = part1(&input);
-        assert_eq!(result, 218433348, "Failed on my input case for part1");
+    fn part2(input: &Self::Input, params: &Self::Params) -> Answer {
+        part2(input, params).into()
     }
 
+    fn pairs() -> Vec<Pair<Self::Input, Self::Params>> {
+        vec![Pair {
+            name: "first tree",
+            reference: |input, params| {
+                let (width, height) = (params.width, params.height);
+                let period = lcm(width as i64, height as i64) as i32;
+                (1..=period)
+                    .find(|&second| draws_tree(input, second, width, height))
+                    .into()
+            },
+            optimised: Self::part2,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::params::Params as _;
+    use crate::aoc::testing::solution_tests;
+    use std::collections::BTreeMap;
+
+    solution_tests!(Day14);
+
     #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day14/input.txt");
+    fn test_parse_errors() {
+        let error = parse_input("p=0,4 v=3,-3\np=6,3 v=-1\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 11));
+        assert_eq!(error.expected, "`,`");
+
+        let error = parse_input("p=0,4 v=3,x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 11));
+        assert_eq!(error.expected, "a number");
+    }
 
-        let result = part2(&input);
-        assert_eq!(result, 6512, "Failed on my input case for part1");
+    #[test]
+    fn test_rejects_an_empty_room() {
+        let overrides = |key: &str| BTreeMap::from([(key.to_string(), "0".to_string())]);
+        assert!(Params::with_overrides(&overrides("width")).is_err());
+        assert!(Params::with_overrides(&overrides("height")).is_err());
+        assert!(Params::with_overrides(&overrides("seconds")).is_ok());
     }
 }
@@ -1,121 +1,86 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::hash::Hash;
-
-pub fn part1(input_map: &str) -> usize {
-    let mut grid: Vec<Vec<char>> = Vec::new();
-    let mut moves = String::new();
-    let mut st = false;
-
-    // Process the input map
-    for line in input_map.lines() {
-        if line.trim().is_empty() {
-            st = true;
-            continue;
-        }
-        if st {
-            moves.push_str(line.trim());
-        } else {
-            grid.push(line.trim().chars().collect());
-        }
-    }
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::{require, ParseError};
+use crate::aoc::solution::Solution;
+use crate::utils::geom::{Direction, Point};
+use crate::utils::grid::Grid;
+use std::collections::{HashSet, VecDeque};
+
+pub struct Warehouse {
+    grid: Grid<char>,
+    moves: String,
+}
 
-    let n = grid.len();
-    let m = grid[0].len();
+pub fn parse_input(input_map: &str) -> Result<Warehouse, ParseError> {
+    require(input_map, '@')?;
 
-    // Find the initial location of "@"
-    let mut cur_loc = None;
-    for i in 0..n {
-        for j in 0..m {
-            if grid[i][j] == '@' {
-                cur_loc = Some((i, j));
-                break;
-            }
-        }
-    }
+    // The map comes first, followed by a blank line and the moves
+    let (map, moves) = input_map.split_once("\n\n").unwrap_or((input_map, ""));
+    let grid = Grid::chars(map.trim_end())?;
+    let moves = moves.lines().map(str::trim).collect();
 
-    // Define the direction mappings with i32 instead of usize
-    let mut symtodir = HashMap::new();
-    symtodir.insert('>', (0, 1)); // Right (no negative values)
-    symtodir.insert('<', (0, -1)); // Left (negative x direction)
-    symtodir.insert('^', (-1, 0)); // Up (negative y direction)
-    symtodir.insert('v', (1, 0)); // Down (positive y direction)
-
-    // Move processing
-    for move_sym in moves.chars() {
-        if let Some(dir) = symtodir.get(&move_sym) {
-            let newloc = (
-                cur_loc.unwrap().0 as i32 + dir.0,
-                cur_loc.unwrap().1 as i32 + dir.1,
-            );
-            let mut finalloc = newloc;
-
-            // Ensure we stay within bounds using wrapping (modular arithmetic)
-            while grid[finalloc.0 as usize][finalloc.1 as usize] != '.'
-                && grid[finalloc.0 as usize][finalloc.1 as usize] != '#'
-            {
-                finalloc = (finalloc.0 + dir.0, finalloc.1 + dir.1);
-            }
+    Ok(Warehouse { grid, moves })
+}
 
-            if grid[finalloc.0 as usize][finalloc.1 as usize] != '#' {
-                let (cur_x, cur_y) = cur_loc.unwrap();
-                let (new_x, new_y) = newloc;
+pub fn part1(warehouse: &Warehouse) -> usize {
+    let mut grid = warehouse.grid.clone();
+    let moves = &warehouse.moves;
 
-                assert!(grid[new_x as usize][new_y as usize] != '#'); // Sanity check
-                grid[finalloc.0 as usize][finalloc.1 as usize] =
-                    grid[new_x as usize][new_y as usize];
-                grid[new_x as usize][new_y as usize] = '@';
-                grid[cur_x as usize][cur_y as usize] = '.';
-                cur_loc = Some((new_x as usize, new_y as usize)); // Update the current location
-            }
+    // Find the initial location of "@"
+    let mut cur_loc = grid.find(&'@').expect("Robot not found");
+
+    // Move processing; the warehouse is walled in, so no step leaves the grid
+    for dir in moves.chars().filter_map(Direction::from_char) {
+        let step = |loc| {
+            grid.step(loc, dir.delta())
+                .expect("the warehouse is walled in")
+        };
+        let newloc = step(cur_loc);
+        let mut finalloc = newloc;
+
+        while grid[finalloc] != '.' && grid[finalloc] != '#' {
+            finalloc = step(finalloc);
         }
-    }
 
-    // Calculate the result based on the 'O' positions
-    let mut ans = 0;
-    for i in 0..n {
-        for j in 0..m {
-            if grid[i][j] == 'O' {
-                ans += 100 * i + j;
-            }
+        if grid[finalloc] != '#' {
+            assert!(grid[newloc] != '#'); // Sanity check
+            grid[finalloc] = grid[newloc];
+            grid[newloc] = '@';
+            grid[cur_loc] = '.';
+            cur_loc = newloc; // Update the current location
         }
     }
 
-    // Return the result
-    ans
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct Position {
-    i: usize,
-    j: usize,
+    // Calculate the result based on the 'O' positions
+    grid.find_all(&'O').map(|p| 100 * p.y + p.x).sum()
 }
 
+/// The warehouse of part 2, where everything but the robot is twice as wide. A box is
+/// stored at its left half.
 #[derive(Debug)]
 struct State {
-    player: Position,
-    boxes: Vec<Position>,
-    walls: HashSet<Position>,
-    grid_size: usize,
+    player: Point<usize>,
+    boxes: Vec<Point<usize>>,
+    walls: HashSet<Point<usize>>,
+    height: usize,
+    width: usize,
 }
 
 impl State {
-    fn new(grid: &Vec<Vec<char>>) -> Self {
-        let n = grid.len();
-        let mut player = Position { i: 0, j: 0 };
+    fn new(grid: &Grid<char>) -> Self {
+        let mut player = Point::new(0, 0);
         let mut boxes = Vec::new();
         let mut walls = HashSet::new();
 
-        for i in 0..n {
-            for j in 0..n {
-                match grid[i][j] {
-                    '@' => player = Position { i, j: j * 2 },
-                    'O' => boxes.push(Position { i, j: j * 2 }),
-                    '#' => {
-                        walls.insert(Position { i, j: j * 2 });
-                        walls.insert(Position { i, j: j * 2 + 1 });
-                    }
-                    _ => {}
+        for (position, &cell) in grid.iter() {
+            let wide = Point::new(position.x * 2, position.y);
+            match cell {
+                '@' => player = wide,
+                'O' => boxes.push(wide),
+                '#' => {
+                    walls.insert(wide);
+                    walls.insert(wide + Point::new(1, 0));
                 }
+                _ => {}
             }
         }
 
@@ -123,83 +88,59 @@ impl State {
             player,
             boxes,
             walls,
-            grid_size: n,
+            height: grid.height(),
+            width: 2 * grid.width(),
         }
     }
 
-    fn in_grid(&self, i: i32, j: i32) -> bool {
-        i >= 0 && i < self.grid_size as i32 && j >= 0 && j < (2 * self.grid_size) as i32
+    /// The position one step in `dir`, if it is still inside the warehouse.
+    fn step(&self, position: Point<usize>, dir: Direction) -> Option<Point<usize>> {
+        position
+            .step(dir)
+            .filter(|p| p.x < self.width && p.y < self.height)
     }
 
-    fn move_player(&mut self, dir: (i32, i32)) {
-        let new_i = self.player.i as i32 + dir.0;
-        let new_j = self.player.j as i32 + dir.1;
-
-        if !self.in_grid(new_i, new_j) {
+    fn move_player(&mut self, dir: Direction) {
+        let Some(new_pos) = self.step(self.player, dir) else {
             return;
-        }
-
-        let new_pos = Position {
-            i: new_i as usize,
-            j: new_j as usize,
         };
 
         if self.walls.contains(&new_pos) {
             return;
         }
 
+        let right_half = Point::new(1, 0);
         let mut stack = VecDeque::new();
         let mut seen = HashSet::new();
 
         for &box_pos in &self.boxes {
-            let position = Position {
-                i: new_i as usize,
-                j: new_j as usize - 1,
-            };
-            if box_pos == new_pos || box_pos == position {
+            if box_pos == new_pos || box_pos + right_half == new_pos {
                 stack.push_back(box_pos);
             }
         }
 
         let mut can_move = true;
         while let Some(top) = stack.pop_front() {
-            let next_i = top.i as i32 + dir.0;
-            let next_j = top.j as i32 + dir.1;
-
-            if !self.in_grid(next_i, next_j)
-                || self.walls.contains(&Position {
-                    i: next_i as usize,
-                    j: next_j as usize,
-                })
-                || self.walls.contains(&Position {
-                    i: next_i as usize,
-                    j: next_j as usize + 1,
-                })
-            {
-                can_move = false;
-                break;
-            }
+            let next = match self.step(top, dir) {
+                Some(next)
+                    if !self.walls.contains(&next)
+                        && !self.walls.contains(&(next + right_half)) =>
+                {
+                    next
+                }
+                _ => {
+                    can_move = false;
+                    break;
+                }
+            };
 
-            if seen.contains(&top) {
+            if !seen.insert(top) {
                 continue;
             }
 
-            seen.insert(top);
-
+            // Boxes overlapping either half of the moved box, other than itself
             for &box_pos in &self.boxes {
-                let position = Position {
-                    i: new_i as usize,
-                    j: new_j as usize,
-                };
-                let position_2 = Position {
-                    i: new_i as usize,
-                    j: new_j as usize - 1,
-                };
-                let position_3 = Position {
-                    i: new_i as usize,
-                    j: new_j as usize + 1,
-                };
-                if box_pos == position || box_pos == position_2 || box_pos == position_3 {
+                if box_pos == next || box_pos + right_half == next || box_pos == next + right_half {
                     stack.push_back(box_pos);
                 }
             }
@@ -208,74 +149,56 @@ impl State {
         if can_move {
             for box_pos in &mut self.boxes {
                 if seen.contains(box_pos) {
-                    box_pos.i = (box_pos.i as i32 + dir.0) as usize;
-                    box_pos.j = (box_pos.j as i32 + dir.1) as usize;
+                    *box_pos = box_pos.step(dir).unwrap();
                 }
             }
-            self.player.i = new_i as usize;
-            self.player.j = new_j as usize;
+            self.player = new_pos;
         }
     }
 
     fn calculate_score(&self) -> i32 {
         self.boxes
             .iter()
-            .map(|pos| (pos.i as i32) * 100 + (pos.j as i32))
+            .map(|pos| (pos.y as i32) * 100 + (pos.x as i32))
             .sum()
     }
 }
 
-pub fn part2(input_map: &str) -> i32 {
-    let parts: Vec<&str> = input_map.trim().split("\n\n").collect();
-    let grid: Vec<Vec<char>> = parts[0]
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect();
-    let steps = parts[1].replace('\n', "");
-
-    let mut state = State::new(&grid);
+pub fn part2(warehouse: &Warehouse) -> i32 {
+    let mut state = State::new(&warehouse.grid);
 
-    let directions = [('<', (0, -1)), ('v', (1, 0)), ('>', (0, 1)), ('^', (-1, 0))];
-
-    for step in steps.chars() {
-        if let Some(&(_, dir)) = directions.iter().find(|&&(d, _)| d == step) {
-            state.move_player(dir);
-        }
+    for dir in warehouse.moves.chars().filter_map(Direction::from_char) {
+        state.move_player(dir);
     }
 
     state.calculate_score()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day15;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day15 {
+    type Input = Warehouse;
+    type Params = ();
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day15/example.txt");
+    const DAY: u8 = 15;
 
-        let result = part1(&input);
-        assert_eq!(result, 10092, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day15/input.txt");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
+    }
 
-        let result = part1(&input);
-        assert_eq!(result, 1429911, "Failed on my input case for part1");
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
 
-    #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day15/input.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
 
-        let result = part2(&input);
-        assert_eq!(result, 1425081, "Failed on my input case for part1");
-    }
+    solution_tests!(Day15);
 }
@@ -1,6 +1,20 @@
-pub fn part1(input_map: &str) -> String {
-    let (mut register_a, mut register_b, mut register_c, program): (i64, i64, i64, Vec<u8>) =
-        parse_input(input_map);
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::{number, ParseError};
+use crate::aoc::solution::Solution;
+
+#[derive(Debug)]
+pub struct Computer {
+    register_a: i64,
+    register_b: i64,
+    register_c: i64,
+    program: Vec<u8>,
+}
+
+pub fn part1(computer: &Computer) -> String {
+    let mut register_a = computer.register_a;
+    let mut register_b = computer.register_b;
+    let mut register_c = computer.register_c;
+    let program = &computer.program;
 
     let mut outputs: Vec<i64> = Vec::new();
     let mut ip: usize = 0; // Instruction pointer
@@ -43,28 +57,39 @@ pub fn part1(input_map: &str) -> String {
         .join(",")
 }
 
-fn parse_input(input: &str) -> (i64, i64, i64, Vec<u8>) {
+pub fn parse_input(input: &str) -> Result<Computer, ParseError> {
     let mut register_a = 0;
     let mut register_b = 0;
     let mut register_c = 0;
     let mut program = Vec::new();
 
-    for line in input.lines() {
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
         match line.split_once(": ") {
-            Some(("Register A", value)) => register_a = value.trim().parse().unwrap(),
-            Some(("Register B", value)) => register_b = value.trim().parse().unwrap(),
-            Some(("Register C", value)) => register_c = value.trim().parse().unwrap(),
+            Some(("Register A", value)) => register_a = number(input, value.trim())?,
+            Some(("Register B", value)) => register_b = number(input, value.trim())?,
+            Some(("Register C", value)) => register_c = number(input, value.trim())?,
             Some(("Program", value)) => {
                 program = value
                     .split(',')
-                    .map(|num| num.trim().parse().unwrap())
-                    .collect()
+                    .map(|num| number(input, num.trim()))
+                    .collect::<Result<_, _>>()?
+            }
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "`Register <X>: ` or `Program: `",
+                ))
             }
-            _ => {}
         }
     }
 
-    (register_a, register_b, register_c, program)
+    Ok(Computer {
+        register_a,
+        register_b,
+        register_c,
+        program,
+    })
 }
 
 fn get_combo_value(operand: u8, a: i64, b: i64, c: i64) -> i64 {
@@ -77,8 +102,10 @@ fn get_combo_value(operand: u8, a: i64, b: i64, c: i64) -> i64 {
     }
 }
 
-pub fn part2(input: &str) -> i64 {
-    let (_, register_b, register_c, program) = parse_input(input); // Unwrap the input
+/// The lowest register A that makes the program print itself, if there is one.
+pub fn part2(computer: &Computer) -> Option<i64> {
+    let register_b = computer.register_b;
+    let register_c = computer.register_c;
 
     // Inline search function
     fn search(a: i64, b: i64, c: i64, program: &[u8], len: usize) -> Option<i64> {
@@ -96,7 +123,7 @@ pub fn part2(input: &str) -> i64 {
         })
     }
 
-    search(0, register_b, register_c, &program, 1).unwrap() // Call and unwrap search result
+    search(0, register_b, register_c, &computer.program, 1)
 }
 
 fn run_program_state(mut a: i64, mut b: i64, mut c: i64, program: &[u8]) -> Vec<u8> {
@@ -128,49 +155,51 @@ fn run_program_state(mut a: i64, mut b: i64, mut c: i64, program: &[u8]) -> Vec<
     output
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Computer;
+    type Params = ();
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
+    const DAY: u8 = 17;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day17/example.txt");
-
-        let result = part1(&input);
-        assert_eq!(
-            result, "4,6,3,5,6,3,5,2,1,0",
-            "Failed on example case for part1"
-        );
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day17/input.txt");
-
-        let result = part1(&input);
-        assert_eq!(
-            result, "2,0,7,3,0,3,1,3,7",
-            "Failed on my input case for part1"
-        );
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
+
+    solution_tests!(Day17);
 
     #[test]
     fn test_example_case_part2() {
         let input = "Register A: 2024\nRegister B: 0\nRegister C: 0\nProgram: 0,3,5,4,3,0";
-        let result = part2(&input);
-        assert_eq!(result, 117440);
+        let result = part2(&parse_input(input).unwrap());
+        assert_eq!(result, Some(117440));
+
+        // Prints a single number, so it can never print its two numbers
+        let input = "Register A: 0\nRegister B: 0\nRegister C: 0\nProgram: 5,4";
+        assert_eq!(part2(&parse_input(input).unwrap()), None);
     }
 
     #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day17/input.txt");
+    fn test_parse_errors() {
+        let error = parse_input("Register A: 729\nProgram: 0,1,x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 14));
 
-        let result = part2(&input);
-        assert_eq!(result, 247839539763386, "Failed on my input case for part1");
+        let error = parse_input("Register A: 729\nRegister D: 0\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
     }
 }
@@ -1,145 +1,109 @@
-use std::collections::{HashSet, VecDeque};
-
-pub fn parse_input(input: &str) -> (Vec<(usize, usize)>, usize) {
-    let mut coordinates = Vec::new();
-    let mut largest_value = 0;
-
-    for line in input.lines() {
-        let parts: Vec<usize> = line
-            .trim()
-            .split(',')
-            .filter_map(|x| x.parse().ok()) // Parse safely and filter invalid numbers
-            .collect();
-
-        if parts.len() < 2 {
-            panic!(
-                "Invalid line format: each line must contain two comma-separated numbers. Got: {}",
-                line
-            );
-        }
+use crate::aoc::answer::Answer;
+use crate::aoc::params::{params, positive};
+use crate::aoc::parse::{number, split_once, ParseError};
+use crate::aoc::solution::{Pair, Solution};
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+use crate::utils::search::{astar, bfs};
+
+params! {
+    pub struct Params {
+        /// Width and height of the memory space.
+        size: usize = 71,
+        /// Bytes that have fallen when part 1 looks for the shortest path.
+        fallen: usize = 1024,
+    }
 
-        let x = parts[0];
-        let y = parts[1];
-        largest_value = largest_value.max(x.max(y));
-        coordinates.push((x, y));
+    fn check(&self) -> Result<(), String> {
+        positive(&[("size", self.size)])
     }
+}
 
-    (coordinates, largest_value + 1)
+pub fn parse_input(input: &str) -> Result<Vec<Point<usize>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (x, y) = split_once(input, line.trim(), ",")?;
+            Ok(Point::new(number(input, x)?, number(input, y)?))
+        })
+        .collect()
 }
 
-pub fn part1(input_map: &str) -> usize {
-    let (coordinates, size) = parse_input(input_map);
-    let blocked: HashSet<(usize, usize)> = coordinates
-        .iter()
-        .take(1024)
-        .filter(|&&(x, y)| x < size && y < size)
-        .copied()
-        .collect();
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-
-    queue.push_back((0, 0, 0));
-    visited.insert((0, 0));
-
-    while let Some((x, y, steps)) = queue.pop_front() {
-        if (x, y) == (size - 1, size - 1) {
-            return steps;
-        }
-
-        for &(dx, dy) in &[(0, 1), (1, 0), (0, -1), (-1, 0)] {
-            let new_x = x as isize + dx;
-            let new_y = y as isize + dy;
-
-            if new_x >= 0 && new_y >= 0 {
-                let new_pos = (new_x as usize, new_y as usize);
-                if new_x < size as isize
-                    && new_y < size as isize
-                    && !blocked.contains(&new_pos)
-                    && !visited.contains(&new_pos)
-                {
-                    visited.insert(new_pos);
-                    queue.push_back((new_x as usize, new_y as usize, steps + 1));
-                }
-            }
+/// The memory space after the first `fallen` bytes, with corrupted cells set.
+fn corrupted(bytes: &[Point<usize>], fallen: usize, size: usize) -> Grid<bool> {
+    let mut grid = Grid::filled(size, size, false);
+    for &byte in bytes.iter().take(fallen) {
+        if let Some(cell) = grid.get_mut(byte) {
+            *cell = true;
         }
     }
-
-    0 // Return 0 if no path found
+    grid
 }
 
-pub fn has_path_to_end(blocked_positions: &HashSet<(usize, usize)>, size: usize) -> bool {
-    let start = (0, 0);
-    let end = (size - 1, size - 1);
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-
-    queue.push_back(start);
-    visited.insert(start);
-
-    while let Some((x, y)) = queue.pop_front() {
-        if (x, y) == end {
-            return true;
-        }
+pub fn part1(bytes: &[Point<usize>], params: &Params) -> usize {
+    let size = params.size;
+    let blocked = corrupted(bytes, params.fallen, size);
+    let end = Point::new(size - 1, size - 1);
+
+    let steps = |&position: &Point<usize>| {
+        blocked
+            .neighbours4(position)
+            .filter(|&next| !blocked[next])
+            .map(|next| (next, 1))
+    };
+
+    astar(
+        Point::new(0, 0),
+        steps,
+        |position| position.manhattan(end),
+        |&position| position == end,
+    )
+    .map_or(0, |(steps, _)| steps) // 0 if no path found
+}
 
-        for &(dx, dy) in &[(0, 1), (1, 0), (0, -1), (-1, 0)] {
-            let new_x = x as isize + dx;
-            let new_y = y as isize + dy;
-
-            if new_x >= 0 && new_y >= 0 {
-                let new_pos = (new_x as usize, new_y as usize);
-                if new_x < size as isize
-                    && new_y < size as isize
-                    && !blocked_positions.contains(&new_pos)
-                    && !visited.contains(&new_pos)
-                {
-                    visited.insert(new_pos);
-                    queue.push_back(new_pos);
-                }
-            }
-        }
-    }
+pub fn has_path_to_end(blocked: &Grid<bool>) -> bool {
+    let end = Point::new(blocked.width() - 1, blocked.height() - 1);
+    let free =
+        |&position: &Point<usize>| blocked.neighbours4(position).filter(|&next| !blocked[next]);
 
-    false
+    bfs([Point::new(0, 0)], free).distances.contains_key(&end)
 }
 
-pub fn part2(input_map: &str) -> Option<String> {
-    let (coordinates, size) = parse_input(input_map);
-    let mut left = 0;
-    let mut right = coordinates.len() - 1;
-    let mut last_blocking = None;
-
-    while left <= right {
-        let mid = (left + right) / 2;
-        let test_blocked: HashSet<(usize, usize)> = coordinates
-            .iter()
-            .take(mid + 1)
-            .filter(|&&(x, y)| x < size && y < size)
-            .copied()
-            .collect();
-
-        if has_path_to_end(&test_blocked, size) {
-            left = mid + 1;
+pub fn part2(coordinates: &[Point<usize>], size: usize) -> Option<String> {
+    // The fewest bytes that block the way lie in (low, high], or nowhere when high is past the end
+    let (mut low, mut high) = (0, coordinates.len() + 1);
+    while high - low > 1 {
+        let mid = (low + high) / 2;
+        if has_path_to_end(&corrupted(coordinates, mid, size)) {
+            low = mid;
         } else {
-            right = mid - 1;
-            last_blocking = Some(mid);
+            high = mid;
         }
     }
 
-    if let Some(last_blocking) = last_blocking {
-        let mut prev_blocked: HashSet<(usize, usize)> = coordinates
-            .iter()
-            .take(last_blocking)
-            .filter(|&&(x, y)| x < size && y < size)
-            .copied()
-            .collect();
-
-        for &(x, y) in &coordinates[last_blocking..last_blocking + 2] {
-            if x >= size || y >= size {
-                continue;
-            }
-            prev_blocked.insert((x, y));
-            if !has_path_to_end(&prev_blocked, size) {
-                return Some(format!("{},{}", x, y));
+    let byte = coordinates.get(high - 1)?;
+    Some(format!("{},{}", byte.x, byte.y))
+}
+
+/// Part 2 by dropping the bytes one at a time, to check the binary search. A path only
+/// needs looking for again once a byte lands on the last one found.
+fn first_blocking_byte(coordinates: &[Point<usize>], size: usize) -> Option<String> {
+    let end = Point::new(size - 1, size - 1);
+    let mut blocked = Grid::filled(size, size, false);
+    let mut path = Vec::new();
+
+    for &byte in coordinates {
+        let Some(cell) = blocked.get_mut(byte) else {
+            continue;
+        };
+        *cell = true;
+        if path.is_empty() || path.contains(&byte) {
+            let free = |&position: &Point<usize>| {
+                blocked.neighbours4(position).filter(|&next| !blocked[next])
+            };
+            match bfs([Point::new(0, 0)], free).path(&end) {
+                Some(found) => path = found,
+                None => return Some(format!("{},{}", byte.x, byte.y)),
             }
         }
     }
@@ -147,44 +111,66 @@ pub fn part2(input_map: &str) -> Option<String> {
     None
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day18;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day18 {
+    type Input = Vec<Point<usize>>;
+    type Params = Params;
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day18/example.txt");
+    const DAY: u8 = 18;
 
-        let result = part1(&input);
-        assert_eq!(result, 0, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day18/input.txt");
-
-        let result = part1(&input);
-        assert_eq!(result, 250, "Failed on my input case for part1");
+    fn part1(input: &Self::Input, params: &Self::Params) -> Answer {
+        part1(input, params).into()
     }
 
-    #[test]
-    fn test_my_input_case_part22() {
-        let input = load_input("input/day18/example.txt");
+    fn part2(input: &Self::Input, params: &Self::Params) -> Answer {
+        part2(input, params.size).into()
+    }
 
-        let result = part2(&input).unwrap();
-        assert_eq!(result, "6,1", "Failed on my input case for part1");
+    fn pairs() -> Vec<Pair<Self::Input, Self::Params>> {
+        vec![
+            Pair {
+                name: "path exists",
+                reference: |input, params| {
+                    let blocked = corrupted(input, params.fallen, params.size);
+                    usize::from(has_path_to_end(&blocked)).into()
+                },
+                optimised: |input, params| usize::from(part1(input, params) > 0).into(),
+            },
+            Pair {
+                name: "blocking byte",
+                reference: |input, params| first_blocking_byte(input, params.size).into(),
+                optimised: Self::part2,
+            },
+        ]
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::params::Params as _;
+    use crate::aoc::testing::solution_tests;
+    use std::collections::BTreeMap;
+
+    solution_tests!(Day18);
 
     #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day18/input.txt");
+    fn test_rejects_an_empty_memory_space() {
+        let overrides = |key: &str| BTreeMap::from([(key.to_string(), "0".to_string())]);
+        assert!(Params::with_overrides(&overrides("size")).is_err());
+        assert!(Params::with_overrides(&overrides("fallen")).is_ok());
+    }
 
-        let result = part2(&input).unwrap();
-        assert_eq!(result, "56,8", "Failed on my input case for part1");
+    #[test]
+    fn test_first_byte_blocking() {
+        let bytes = [Point::new(1, 1), Point::new(0, 1)];
+        assert_eq!(part2(&bytes, 2), Some("1,1".to_string()));
+        assert_eq!(part2(&bytes[1..], 2), None);
+        assert_eq!(part2(&[], 2), None);
     }
 }
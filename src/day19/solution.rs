@@ -1,46 +1,54 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::{Pair, Solution};
 use std::collections::HashSet;
 
-pub fn parse_input(input: &str) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
-    let mut sections = input.split("\n\n");
+#[derive(Debug)]
+pub struct Onsen {
+    towels: Vec<Vec<usize>>,
+    patterns: Vec<Vec<usize>>,
+}
+
+pub fn parse_input(input: &str) -> Result<Onsen, ParseError> {
+    let (towels, patterns) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::at(
+            input,
+            &input[input.len()..],
+            "a blank line before the patterns",
+        )
+    })?;
 
-    let towels = sections
-        .next()
-        .unwrap()
+    let towels = towels
         .lines()
-        .map(|line| {
-            line.split(", ")
-                .map(|c| {
-                    c.chars()
-                        .map(|ch| "wubrg".find(ch).unwrap())
-                        .collect::<Vec<usize>>()
-                })
-                .collect::<Vec<Vec<usize>>>()
-        })
-        .flatten() // Flatten to make it a Vec<Vec<usize>>
-        .collect();
+        .flat_map(|line| line.split(", "))
+        .map(|towel| parse_stripes(input, towel))
+        .collect::<Result<_, _>>()?;
 
-    let patterns = sections
-        .next()
-        .unwrap()
+    let patterns = patterns
         .lines()
-        .map(|line| {
-            line.chars()
-                .map(|ch| "wubrg".find(ch).unwrap())
-                .collect::<Vec<usize>>()
-        })
-        .collect();
+        .map(|line| parse_stripes(input, line))
+        .collect::<Result<_, _>>()?;
 
-    (towels, patterns)
+    Ok(Onsen { towels, patterns })
 }
 
-pub fn part1(input_map: &str) -> usize {
-    let input = parse_input(input_map);
+/// Maps each stripe colour of `text`, a slice of `input`, to its index in `wubrg`.
+fn parse_stripes(input: &str, text: &str) -> Result<Vec<usize>, ParseError> {
+    text.char_indices()
+        .map(|(index, ch)| {
+            "wubrg"
+                .find(ch)
+                .ok_or_else(|| ParseError::at(input, &text[index..], "one of `wubrg`"))
+        })
+        .collect()
+}
 
+pub fn part1(input: &Onsen) -> usize {
     // Initialize towels as a vector of hash sets
     let mut towels: Vec<HashSet<Vec<usize>>> = vec![];
 
     // Fill towels with the provided input
-    for towel in &input.0 {
+    for towel in &input.towels {
         while towels.len() <= towel.len() {
             towels.push(HashSet::new());
         }
@@ -50,7 +58,7 @@ pub fn part1(input_map: &str) -> usize {
     let mut count = 0;
 
     // For each pattern, check if it's reachable
-    for pattern in &input.1 {
+    for pattern in &input.patterns {
         let mut reachable = vec![false; pattern.len() + 1];
         reachable[0] = true;
 
@@ -73,12 +81,11 @@ pub fn part1(input_map: &str) -> usize {
     count
 }
 
-pub fn part2(input_map: &str) -> usize {
-    let input = parse_input(input_map);
-    let towels = build_towels(&input.0);
+pub fn part2(input: &Onsen) -> usize {
+    let towels = build_towels(&input.towels);
 
     let mut count = 0;
-    for pattern in &input.1 {
+    for pattern in &input.patterns {
         count += count_reachable_patterns(pattern, &towels);
     }
 
@@ -115,44 +122,53 @@ fn count_reachable_patterns(pattern: &[usize], towels: &[HashSet<Vec<usize>>]) -
     reachable[pattern.len()]
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day19;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day19 {
+    type Input = Onsen;
+    type Params = ();
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day19/example.txt");
+    const DAY: u8 = 19;
 
-        let result = part1(&input);
-        assert_eq!(result, 6, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day19/input.txt");
-
-        let result = part1(&input);
-        assert_eq!(result, 313, "Failed on my input case for part1");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
     }
 
-    #[test]
-    fn test_my_input_case_part22() {
-        let input = load_input("input/day19/example.txt");
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
+    }
 
-        let result = part2(&input);
-        assert_eq!(result, 16, "Failed on my input case for part1");
+    fn pairs() -> Vec<Pair<Self::Input, Self::Params>> {
+        vec![Pair {
+            name: "possible designs",
+            reference: |input, _| {
+                let towels = build_towels(&input.towels);
+                let possible =
+                    |pattern: &&Vec<usize>| count_reachable_patterns(pattern, &towels) > 0;
+                input.patterns.iter().filter(possible).count().into()
+            },
+            optimised: Self::part1,
+        }]
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
+
+    solution_tests!(Day19);
 
     #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day19/input.txt");
+    fn test_parse_errors() {
+        let error = parse_input("r, wr, bx\n\nbrwrr\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+        assert_eq!(error.expected, "one of `wubrg`");
 
-        let result = part2(&input);
-        assert_eq!(result, 666491493769758, "Failed on my input case for part1");
+        assert!(parse_input("r, wr, b\n").is_err());
     }
 }
@@ -1,20 +1,27 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::Solution;
 use crate::utils;
 
-pub fn part1(input: &str) -> usize {
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
     utils::strings::split_array_input_into_vector(input)
-        .into_iter()
+}
+
+pub fn part1(reports: &[Vec<i32>]) -> usize {
+    reports
+        .iter()
         .filter(|report| is_safe_report(report))
         .count()
 }
 
-pub fn part2(input: &str) -> usize {
-    utils::strings::split_array_input_into_vector(input)
-        .into_iter()
+pub fn part2(reports: &[Vec<i32>]) -> usize {
+    reports
+        .iter()
         .filter(|report| is_safe_with_dampener(report))
         .count()
 }
 
-fn is_safe_report(report: &Vec<i32>) -> bool {
+fn is_safe_report(report: &[i32]) -> bool {
     for i in 1..report.len() {
         let diff = report[i] - report[i - 1];
         if diff.abs() < 1
@@ -28,52 +35,42 @@ fn is_safe_report(report: &Vec<i32>) -> bool {
     true
 }
 
-fn is_safe_with_dampener(report: &Vec<i32>) -> bool {
+fn is_safe_with_dampener(report: &[i32]) -> bool {
     if is_safe_report(report) {
         return true;
     }
 
     (0..report.len()).any(|i| {
-        let modified_report = utils::vecs::remove_at_index(&report, i);
+        let modified_report = utils::vecs::remove_at_index(report, i);
         is_safe_report(&modified_report)
     })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    #[test]
-    fn test_example_case() {
-        let input =
-            fs::read_to_string("input/day2/example.txt").expect("Failed to read input file");
+pub struct Day2;
 
-        let result = part1(&input);
-        assert_eq!(result, 2);
-    }
+impl Solution for Day2 {
+    type Input = Vec<Vec<i32>>;
+    type Params = ();
 
-    #[test]
-    fn test_my_input_example_case() {
-        let input = fs::read_to_string("input/day2/input.txt").expect("Failed to read input file");
+    const DAY: u8 = 2;
 
-        let result = part1(&input);
-        assert_eq!(result, 490);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_example_case_part_2() {
-        let input =
-            fs::read_to_string("input/day2/example.txt").expect("Failed to read input file");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
+    }
 
-        let result = part2(&input);
-        assert_eq!(result, 4);
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
 
-    #[test]
-    fn test_my_input_case_part_2() {
-        let input = fs::read_to_string("input/day2/input.txt").expect("Failed to read input file");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
 
-        let result = part2(&input);
-        assert_eq!(result, 536);
-    }
+    solution_tests!(Day2);
 }
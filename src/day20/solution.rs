@@ -1,114 +1,78 @@
-pub fn part1(input_map: &str) -> usize {
-    let input: Vec<Vec<usize>> = match parse_input(input_map) {
-        Ok(input) => input,
-        Err(_) => {
-            return 0;
-        }
-    };
-    cheats_that_save_at_least(&input, 100, 2)
+use crate::aoc::answer::Answer;
+use crate::aoc::params::params;
+use crate::aoc::parse::{require, ParseError};
+use crate::aoc::solution::Solution;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+use crate::utils::search::bfs;
+
+params! {
+    pub struct Params {
+        /// Picoseconds a cheat must save to be counted.
+        floor: usize = 100,
+        /// Longest cheat, in picoseconds, in each part.
+        cheat_part1: usize = 2,
+        cheat_part2: usize = 20,
+    }
 }
 
-pub fn part2(input_map: &str) -> usize {
-    let input: Vec<Vec<usize>> = match parse_input(input_map) {
-        Ok(input) => input,
-        Err(_) => {
-            return 0;
-        }
-    };
-    cheats_that_save_at_least(&input, 100, 20)
+pub fn part1(input: &Grid<usize>, params: &Params) -> usize {
+    cheats_that_save_at_least(input, params.floor, params.cheat_part1)
 }
 
-fn parse_input(text: &str) -> Result<Vec<Vec<usize>>, String> {
-    text.lines().map(parse_row).collect()
+pub fn part2(input: &Grid<usize>, params: &Params) -> usize {
+    cheats_that_save_at_least(input, params.floor, params.cheat_part2)
 }
 
-/// Helper function to parse a single row from the input.
-fn parse_row(line: &str) -> Result<Vec<usize>, String> {
-    line.chars().map(parse_char).collect()
+/// Parses the racetrack, which must have a start and an end.
+pub fn parse_input(text: &str) -> Result<Grid<usize>, ParseError> {
+    require(text, 'S')?;
+    require(text, 'E')?;
+    Grid::parse(text, parse_char, "one of `.#SE`")
 }
 
 /// Helper function to parse a single character into its corresponding usize value.
-fn parse_char(ch: char) -> Result<usize, String> {
+fn parse_char(ch: char) -> Option<usize> {
     match ch {
-        '.' => Ok(0),
-        '#' => Ok(1),
-        'S' => Ok(2),
-        'E' => Ok(3),
-        _ => Err(format!("Invalid character in input: {}", ch)),
+        '.' => Some(0),
+        '#' => Some(1),
+        'S' => Some(2),
+        'E' => Some(3),
+        _ => None,
     }
 }
 
-fn cheats_that_save_at_least(input: &Vec<Vec<usize>>, floor: usize, duration: usize) -> usize {
-    let nr = input.len();
-    let nc = input[0].len();
+fn cheats_that_save_at_least(input: &Grid<usize>, floor: usize, duration: usize) -> usize {
+    let nr = input.height();
+    let nc = input.width();
 
-    let mut map = input.clone();
-    let mut start = None;
-    let mut end = None;
+    let start = input.find(&2).unwrap_or_default();
+    let end = input.find(&3).unwrap_or_default();
 
-    // Iterate over the grid and process special cells
-    for (r, row) in map.iter_mut().enumerate() {
-        for (c, ch) in row.iter_mut().enumerate() {
-            match *ch {
-                1 => {
-                    *ch = usize::MAX;
-                }
-                2 => {
-                    *ch = 0;
-                    if start.is_none() {
-                        start = Some((r, c));
-                    }
-                }
-                3 => {
-                    *ch = 0;
-                    if end.is_none() {
-                        end = Some((r, c));
-                    }
-                }
-                _ => {}
-            }
-        }
+    // Distance from every track cell to the end, walls staying unreachable
+    let track = |&point: &Point<usize>| input.neighbours4(point).filter(|&next| input[next] != 1);
+    let to_end = bfs([end], track).distances;
+    let mut map = Grid::filled(nc, nr, usize::MAX);
+    for (&point, &distance) in &to_end {
+        map[point] = distance;
     }
 
-    // Ensure start and end are found, with default values if not.
-    let start = start.unwrap_or((0, 0)); // Handle as needed for missing start
-    let end = end.unwrap_or((0, 0)); // Handle as needed for missing end
-
-    let mut point = end;
-    let mut path = vec![end];
-
-    // Backtrack from end to start
-    while point != start {
-        let mut n = 0;
-        for (rr, cc) in neighbors(nr, nc, point.0, point.1) {
-            if map[rr][cc] == 0 && (rr, cc) != end {
-                point = (rr, cc);
-                n = 1;
-                map[rr][cc] = path.len();
-                path.push((rr, cc));
-            }
-        }
-
-        // If no valid neighbor found, return early
-        if n == 0 {
-            return 0;
-        }
-    }
-
-    let track_len = path.len() - 1;
-    let threshold = track_len - floor;
-
-    path.reverse(); // Reverse path for easier processing later
+    let Some(&track_len) = to_end.get(&start) else {
+        return 0;
+    };
+    let Some(threshold) = track_len.checked_sub(floor) else {
+        return 0;
+    };
 
     let mut count = 0;
 
-    // Process the path to check for cheats
-    for (i, point) in path.iter().copied().enumerate() {
-        let (r, c) = point;
+    // Process the track to check for cheats
+    for (&point, &distance) in &to_end {
+        let i = track_len - distance;
 
-        for (rr, cc) in cheats(nr, nc, r, c, duration) {
-            if map[rr][cc] < usize::MAX {
-                let t = i + r.abs_diff(rr) + c.abs_diff(cc) + map[rr][cc];
+        for target in cheats(nr, nc, point, duration) {
+            if map[target] < usize::MAX {
+                let t = i + point.manhattan(target) + map[target];
 
                 if t <= threshold {
                     count += 1;
@@ -120,39 +84,20 @@ fn cheats_that_save_at_least(input: &Vec<Vec<usize>>, floor: usize, duration: us
     count
 }
 
-fn neighbors(nr: usize, nc: usize, r: usize, c: usize) -> impl Iterator<Item = (usize, usize)> {
-    let potential_neighbors = [
-        (r, c + 1),             // right
-        (r.wrapping_sub(1), c), // up
-        (r, c.wrapping_sub(1)), // left
-        (r + 1, c),             // down
-    ];
-
-    potential_neighbors
-        .into_iter()
-        .filter(move |&(rr, cc)| is_valid_cell(nr, nc, rr, cc))
-}
-
-/// Helper function to check if a given cell (rr, cc) is within grid bounds.
-fn is_valid_cell(nr: usize, nc: usize, r: usize, c: usize) -> bool {
-    r < nr && c < nc
-}
-
 fn cheats(
     nr: usize,
     nc: usize,
-    r: usize,
-    c: usize,
+    point: Point<usize>,
     duration: usize,
-) -> impl Iterator<Item = (usize, usize)> {
+) -> impl Iterator<Item = Point<usize>> {
     // Calculate valid row range, ensuring it's within grid bounds.
-    let row_range = row_range(nr, r, duration);
+    let row_range = row_range(nr, point.y, duration);
 
     // For each row in the valid range, calculate the valid column range.
     row_range.flat_map(move |rr| {
         // Calculate the safe column range based on the row distance.
-        let column_range = column_range(nc, c, duration, r.abs_diff(rr));
-        column_range.map(move |cc| (rr, cc))
+        let column_range = column_range(nc, point.x, duration, point.y.abs_diff(rr));
+        column_range.map(move |cc| Point::new(cc, rr))
     })
 }
 
@@ -172,28 +117,41 @@ fn column_range(
     c.saturating_sub(dc)..=(c + dc).min(nc - 1)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Grid<usize>;
+    type Params = Params;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
+    const DAY: u8 = 20;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day20/input.txt");
+    fn part1(input: &Self::Input, params: &Self::Params) -> Answer {
+        part1(input, params).into()
+    }
 
-        let result = part1(&input);
-        assert_eq!(result, 1459, "Failed on my input case for part1");
+    fn part2(input: &Self::Input, params: &Self::Params) -> Answer {
+        part2(input, params).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
+
+    solution_tests!(Day20);
 
     #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day20/input.txt");
+    fn test_parse_errors() {
+        let error = parse_input("#####\n#S.E#\n#..x#\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 4));
+        assert_eq!(error.expected, "one of `.#SE`");
 
-        let result = part2(&input);
-        assert_eq!(result, 1016066, "Failed on my input case for part1");
+        let error = parse_input("#####\n#S..#\n").unwrap_err();
+        assert_eq!(error.expected, "`E`");
     }
 }
@@ -1,109 +1,141 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::params::{non_negative, params};
+use crate::aoc::parse::{number, ParseError};
+use crate::aoc::solution::Solution;
+use crate::utils::geom::{Direction, Point};
+use crate::utils::memo::Memo;
 use itertools::Itertools;
-use std::collections::HashMap;
 
-type Position = (i64, i64);
+/// A key's column and row on its keypad.
+type Position = Point<isize>;
 
-const DIRECTIONS: [(char, Position); 4] =
-    [('^', (-1, 0)), ('v', (1, 0)), ('<', (0, -1)), ('>', (0, 1))];
-
-fn add_pos(p1: Position, p2: Position) -> Position {
-    (p1.0 + p2.0, p1.1 + p2.1)
-}
+params! {
+    pub struct Params {
+        /// Robots using directional keypads in each part.
+        robots_part1: i64 = 2,
+        robots_part2: i64 = 25,
+    }
 
-fn sub_pos(p1: Position, p2: Position) -> Position {
-    (p1.0 - p2.0, p1.1 - p2.1)
+    fn check(&self) -> Result<(), String> {
+        non_negative(&[("robots_part1", self.robots_part1), ("robots_part2", self.robots_part2)])
+    }
 }
 
-pub fn part1(input: &str) -> i64 {
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
     input
         .lines()
-        .map(|line| line.trim().to_string())
-        .map(|code| calculate_sequence_complexity(&code, 2))
-        .sum()
+        .map(|line| {
+            let code = line.trim();
+            let end = code
+                .find(|ch: char| !ch.is_ascii_digit())
+                .unwrap_or(code.len());
+            let (digits, rest) = code.split_at(end);
+            if digits.is_empty() {
+                return Err(ParseError::at(input, code, "a digit"));
+            }
+            match rest.strip_prefix('A') {
+                Some("") => {}
+                Some(tail) => return Err(ParseError::at(input, tail, "the end of the code")),
+                None => return Err(ParseError::at(input, rest, "a digit or `A`")),
+            }
+            number::<i64>(input, digits)?;
+            Ok(code.to_string())
+        })
+        .collect()
 }
 
-pub fn part2(input: &str) -> i64 {
-    input
-        .lines()
-        .map(|line| line.trim().to_string())
-        .map(|code| calculate_sequence_complexity(&code, 25))
+/// Sum of the complexities of the codes with `robots` directional keypads in between.
+pub fn total_complexity(codes: &[String], robots: i64) -> i64 {
+    let mut memo = Memo::new();
+    codes
+        .iter()
+        .map(|code| {
+            let length = find_shortest(code, robots, 0, &mut memo);
+            length * code[..code.len() - 1].parse::<i64>().unwrap()
+        })
         .sum()
 }
 
-fn calculate_sequence_complexity(code: &str, number_of_robots: i64) -> i64 {
-    let mut cache = HashMap::new();
-    let length = find_shortest(code, number_of_robots, 0, &mut cache);
-    length * code[..code.len() - 1].parse::<i64>().unwrap()
-}
-
-
+/// Fewest presses on the outermost keypad to type `sequence` on the keypad at `depth`,
+/// where depth 0 is the numeric keypad and every robot starts on its activate key.
 fn find_shortest(
     sequence: &str,
     number_of_robots: i64,
     depth: i64,
-    cache: &mut HashMap<(String, i64, i64), i64>,
+    memo: &mut Memo<(char, char, i64), i64>,
 ) -> i64 {
-    let key = (sequence.to_string(), depth, number_of_robots);
-    if let Some(&cached) = cache.get(&key) {
-        return cached;
-    }
-
-    let mut positions = HashMap::new();
-    positions.insert('7', (0, 0));
-    positions.insert('8', (0, 1));
-    positions.insert('9', (0, 2));
-    positions.insert('4', (1, 0));
-    positions.insert('5', (1, 1));
-    positions.insert('6', (1, 2));
-    positions.insert('1', (2, 0));
-    positions.insert('2', (2, 1));
-    positions.insert('3', (2, 2));
-    positions.insert('0', (3, 1));
-    positions.insert('A', (3, 2));
-    positions.insert('^', (0, 1));
-    positions.insert('a', (0, 2));
-    positions.insert('<', (1, 0));
-    positions.insert('v', (1, 1));
-    positions.insert('>', (1, 2));
-
-    let mut curr = if depth == 0 {
-        positions[&'A']
-    } else {
-        positions[&'a']
-    };
-    let mut total_length = 0;
+    let start = if depth == 0 { 'A' } else { 'a' };
+    std::iter::once(start)
+        .chain(sequence.chars())
+        .tuple_windows()
+        .map(|(from, to)| find_shortest_press(from, to, number_of_robots, depth, memo))
+        .sum()
+}
 
-    let avoid = if depth == 0 { (3, 0) } else { (0, 0) };
+/// Fewest presses on the outermost keypad to move from `from` to `to` on the keypad at
+/// `depth` and press it. Only the pair of keys matters, so the cache keys stay small.
+fn find_shortest_press(
+    from: char,
+    to: char,
+    number_of_robots: i64,
+    depth: i64,
+    memo: &mut Memo<(char, char, i64), i64>,
+) -> i64 {
+    memo.get_or_insert_with((from, to, depth), |memo| {
+        // The gap in the bottom left of the numeric keypad and top left of the directional one
+        let avoid = if depth == 0 {
+            Point::new(0, 3)
+        } else {
+            Point::new(0, 0)
+        };
 
-    for char in sequence.chars() {
-        let target = positions[&char];
-        let moves = generate_moves(curr, target, avoid);
+        let moves = generate_moves(key_position(from), key_position(to), avoid);
         if depth == number_of_robots {
-            total_length += moves.iter().map(|m| m.len()).min().unwrap() as i64;
+            moves.iter().map(|m| m.len()).min().unwrap() as i64
         } else {
-            total_length += moves
+            moves
                 .iter()
-                .map(|m| find_shortest(m, number_of_robots, depth + 1, cache))
+                .map(|m| find_shortest(m, number_of_robots, depth + 1, memo))
                 .min()
-                .unwrap();
+                .unwrap()
         }
-        curr = target;
-    }
+    })
+}
 
-    cache.insert(key, total_length);
-    total_length
+/// Where a key is on its keypad. The directional keypad's activate key is written `a` to
+/// tell it apart from the numeric one.
+fn key_position(key: char) -> Position {
+    match key {
+        '7' => Point::new(0, 0),
+        '8' => Point::new(1, 0),
+        '9' => Point::new(2, 0),
+        '4' => Point::new(0, 1),
+        '5' => Point::new(1, 1),
+        '6' => Point::new(2, 1),
+        '1' => Point::new(0, 2),
+        '2' => Point::new(1, 2),
+        '3' => Point::new(2, 2),
+        '0' => Point::new(1, 3),
+        'A' => Point::new(2, 3),
+        '^' => Point::new(1, 0),
+        'a' => Point::new(2, 0),
+        '<' => Point::new(0, 1),
+        'v' => Point::new(1, 1),
+        '>' => Point::new(2, 1),
+        _ => panic!("no key {:?} on the keypads", key),
+    }
 }
 
 fn generate_moves(start: Position, end: Position, avoid: Position) -> Vec<String> {
-    let (dy, dx) = sub_pos(end, start);
+    let Point { x: dx, y: dy } = end - start;
     let mut moves = Vec::new();
     if dy < 0 {
-        moves.extend(vec!["^"; dy.abs() as usize]);
+        moves.extend(vec!["^"; dy.unsigned_abs()]);
     } else {
         moves.extend(vec!["v"; dy as usize]);
     }
     if dx < 0 {
-        moves.extend(vec!["<"; dx.abs() as usize]);
+        moves.extend(vec!["<"; dx.unsigned_abs()]);
     } else {
         moves.extend(vec![">"; dx as usize]);
     }
@@ -113,12 +145,8 @@ fn generate_moves(start: Position, end: Position, avoid: Position) -> Vec<String
         let mut pos = start;
         let mut valid = true;
         for move_ in &perm {
-            let dir = DIRECTIONS
-                .iter()
-                .find(|&&(k, _)| k == move_.chars().next().unwrap())
-                .unwrap()
-                .1;
-            pos = add_pos(pos, dir);
+            let dir = move_.chars().next().and_then(Direction::from_char).unwrap();
+            pos += dir.delta();
             if pos == avoid {
                 valid = false;
                 break;
@@ -137,44 +165,60 @@ fn generate_moves(start: Position, end: Position, avoid: Position) -> Vec<String
     result
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day21;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day21 {
+    type Input = Vec<String>;
+    type Params = Params;
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day21/example.txt");
+    const DAY: u8 = 21;
 
-        let result = part1(&input);
-        assert_eq!(result, 126384, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day21/input.txt");
+    fn part1(input: &Self::Input, params: &Self::Params) -> Answer {
+        total_complexity(input, params.robots_part1).into()
+    }
 
-        let result = part1(&input);
-        assert_eq!(result, 105458, "Failed on my input case for part1");
+    fn part2(input: &Self::Input, params: &Self::Params) -> Answer {
+        total_complexity(input, params.robots_part2).into()
     }
+}
 
-    #[test]
-    fn test_example_case_part2() {
-        let input = load_input("input/day21/example.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::params::Params as _;
+    use crate::aoc::testing::solution_tests;
+    use std::collections::BTreeMap;
+
+    solution_tests!(Day21);
 
-        let result = part2(&input);
-        assert_eq!(result, 154115708116294, "Failed on example case for part2");
+    #[test]
+    fn test_rejects_negative_robots() {
+        let overrides =
+            |key: &str, value: &str| BTreeMap::from([(key.to_string(), value.to_string())]);
+        assert!(Params::with_overrides(&overrides("robots_part1", "-1")).is_err());
+        assert!(Params::with_overrides(&overrides("robots_part2", "-1")).is_err());
+        assert!(Params::with_overrides(&overrides("robots_part1", "0")).is_ok());
     }
 
     #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day21/input.txt");
+    fn test_parse_errors() {
+        let error = parse_input("029A\n-1A\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "a digit");
+
+        let error = parse_input("+1A\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+
+        let error = parse_input("029B\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.expected, "a digit or `A`");
 
-        let result = part2(&input);
-        assert_eq!(result, 129551515895690, "Failed on my input case for part2");
+        let error = parse_input("029AA\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.expected, "the end of the code");
     }
 }
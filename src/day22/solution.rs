@@ -1,11 +1,22 @@
-use std::collections::{HashMap, HashSet};
+use crate::aoc::answer::Answer;
+use crate::aoc::params::params;
+use crate::aoc::parse::{number, ParseError};
+use crate::aoc::solution::Solution;
+use crate::utils::hash::{FxHashMap, FxHashSet};
+
+params! {
+    pub struct Params {
+        /// New secret numbers each buyer generates.
+        iterations: usize = 2000,
+    }
+}
 
-pub fn part1(input: &str) -> i64 {
-    const ITERATIONS: usize = 2000;
+pub fn part1(secrets: &[i64], iterations: usize) -> i64 {
     let mut ans = 0;
 
-    for mut value in extract(input) {
-        for _ in 0..ITERATIONS {
+    for &secret in secrets {
+        let mut value = secret;
+        for _ in 0..iterations {
             value = next_num(value);
         }
         ans += value;
@@ -14,18 +25,17 @@ pub fn part1(input: &str) -> i64 {
     ans
 }
 
-pub fn part2(input: &str) -> i64 {
-    const ITERATIONS: usize = 2000;
-    let mut map = HashMap::new();
+pub fn part2(secrets: &[i64], iterations: usize) -> i64 {
+    let mut map = FxHashMap::default();
 
-    for v in extract(input) {
-        let mut visited = v;
-        let mut seen = HashSet::new();
+    for &secret in secrets {
+        let mut visited = secret;
+        let mut seen = FxHashSet::default();
 
         let mut arr = [0, 0, 0, 0];
         let mut prev_price = 0;
 
-        for iteration in 0..ITERATIONS {
+        for iteration in 0..iterations {
             visited = next_num(visited);
             let price = visited % 10;
             arr = [price - prev_price, arr[0], arr[1], arr[2]];
@@ -41,10 +51,12 @@ pub fn part2(input: &str) -> i64 {
     *map.values().max().expect("No records")
 }
 
-fn extract(input: &str) -> Vec<i64> {
+pub fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
     input
         .lines()
-        .filter_map(|line| line.trim().parse::<i64>().ok())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| number(input, line))
         .collect()
 }
 
@@ -60,44 +72,31 @@ fn next_num(mut x: i64) -> i64 {
     x
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day22;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day22 {
+    type Input = Vec<i64>;
+    type Params = Params;
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day22/example.txt");
+    const DAY: u8 = 22;
 
-        let result = part1(&input);
-        assert_eq!(result, 94558292, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day22/input.txt");
-
-        let result = part1(&input);
-        assert_eq!(result, 19927218456, "Failed on my input case for part1");
+    fn part1(input: &Self::Input, params: &Self::Params) -> Answer {
+        part1(input, params.iterations).into()
     }
 
-    #[test]
-    fn test_my_input_case_part22() {
-        let input = load_input("input/day22/example.txt");
-
-        let result = part2(&input);
-        assert_eq!(result, 90, "Failed on my input case for part1");
+    fn part2(input: &Self::Input, params: &Self::Params) -> Answer {
+        part2(input, params.iterations).into()
     }
+}
 
-    #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day22/input.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
 
-        let result = part2(&input);
-        assert_eq!(result, 2189, "Failed on my input case for part1");
-    }
+    solution_tests!(Day22);
 }
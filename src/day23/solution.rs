@@ -1,13 +1,17 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::{split_once, ParseError};
+use crate::aoc::solution::Solution;
 use std::collections::{HashMap, HashSet};
 
-pub fn part1(input: &str) -> usize {
-    let input = parse(input);
+pub type Network = HashMap<String, Vec<String>>;
+
+pub fn part1(input: &Network) -> usize {
     let mut result = HashSet::new();
 
-    for (&a, neighbors) in &input {
-        for &i in neighbors {
-            for &j in neighbors {
-                if i != j && input.get(i).map_or(false, |x| x.contains(&j)) {
+    for (a, neighbors) in input {
+        for i in neighbors {
+            for j in neighbors {
+                if i != j && input.get(i).is_some_and(|x| x.contains(j)) {
                     let mut triangle = vec![a, i, j];
                     triangle.sort();
                     result.insert(triangle);
@@ -22,37 +26,32 @@ pub fn part1(input: &str) -> usize {
         .count()
 }
 
-fn parse(input: &str) -> HashMap<&str, Vec<&str>> {
-    let mut map: HashMap<&str, Vec<&str>> = HashMap::new();
+pub fn parse_input(input: &str) -> Result<Network, ParseError> {
+    let mut map: Network = HashMap::new();
 
-    for line in input.lines() {
-        match line.split_once('-') {
-            Some((a, b)) => {
-                map.entry(a).or_default().push(b);
-                map.entry(b).or_default().push(a);
-            }
-            None => {}
-        }
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let (a, b) = split_once(input, line, "-")?;
+        map.entry(a.to_string()).or_default().push(b.to_string());
+        map.entry(b.to_string()).or_default().push(a.to_string());
     }
 
-    map
+    Ok(map)
 }
 
-pub fn part2(input: &str) -> String {
-    let input: HashMap<&str, Vec<&str>> = parse(input);
+pub fn part2(input: &Network) -> String {
     let mut result = Vec::new();
 
-    for (&node, neighbors) in &input {
+    for (node, neighbors) in input {
         let nbr_count = neighbors.len();
         for mask in 0..(1 << nbr_count) {
-            let mut nodes = vec![node];
-            for (i, &neighbor) in neighbors.iter().enumerate() {
+            let mut nodes = vec![node.as_str()];
+            for (i, neighbor) in neighbors.iter().enumerate() {
                 if (mask & (1 << i)) != 0 {
-                    nodes.push(neighbor);
+                    nodes.push(neighbor.as_str());
                 }
             }
 
-            if is_clique(&nodes, &input) && nodes.len() > result.len() {
+            if is_clique(&nodes, input) && nodes.len() > result.len() {
                 result = nodes;
             }
         }
@@ -62,13 +61,13 @@ pub fn part2(input: &str) -> String {
     result.join(",")
 }
 
-fn is_clique(nodes: &[&str], adj: &HashMap<&str, Vec<&str>>) -> bool {
+fn is_clique(nodes: &[&str], adj: &Network) -> bool {
     let n = nodes.len();
     for i in 0..n {
         for j in (i + 1)..n {
             if !adj
                 .get(nodes[i])
-                .map_or(false, |neighbors| neighbors.contains(&nodes[j]))
+                .is_some_and(|neighbors| neighbors.iter().any(|other| other == nodes[j]))
             {
                 return false;
             }
@@ -77,47 +76,31 @@ fn is_clique(nodes: &[&str], adj: &HashMap<&str, Vec<&str>>) -> bool {
     true
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day23;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day23 {
+    type Input = Network;
+    type Params = ();
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day23/example.txt");
+    const DAY: u8 = 23;
 
-        let result = part1(&input);
-        assert_eq!(result, 7, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day23/input.txt");
-
-        let result = part1(&input);
-        assert_eq!(result, 1306, "Failed on my input case for part1");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
     }
 
-    #[test]
-    fn test_my_input_case_part22() {
-        let input = load_input("input/day23/example.txt");
-
-        let result = part2(&input);
-        assert_eq!(result, "co,de,ka,ta", "Failed on my input case for part1");
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
 
-    #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day23/input.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
 
-        let result = part2(&input);
-        assert_eq!(
-            result, "bd,dk,ir,ko,lk,nn,ob,pt,te,tl,uh,wj,yl",
-            "Failed on my input case for part1"
-        );
-    }
+    solution_tests!(Day23);
 }
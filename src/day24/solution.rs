@@ -1,47 +1,73 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::{number, ParseError};
+use crate::aoc::solution::Solution;
+use crate::utils::strings::scan;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-pub fn part1(input: &str) -> i64 {
-    let sections: Vec<&str> = input.trim().split("\n\n").collect();
-    let wire_lines = sections[0].lines();
-    let logic_lines = sections[1].lines();
+#[derive(Debug)]
+pub struct Device {
+    wires: Vec<(String, i32)>,
+    gates: Vec<Gate>,
+}
+
+pub fn parse_input(input: &str) -> Result<Device, ParseError> {
+    let (wires_raw, gates_raw) = input.trim().split_once("\n\n").ok_or_else(|| {
+        ParseError::at(
+            input,
+            &input[input.len()..],
+            "a blank line before the gates",
+        )
+    })?;
+
+    // Parse wires
+    let mut wires = Vec::new();
+    for line in wires_raw.lines() {
+        let [name, value] = scan(input, line, "{}: {}")?.fields();
+        wires.push((name.to_string(), number(input, value)?));
+    }
 
-    let mut wires: HashMap<String, i32> = HashMap::new();
+    // Parse gates
+    let mut gates = Vec::new();
+    for line in gates_raw.lines() {
+        let [a, op, b, output] = scan(input, line, "{} {} {} -> {}")?.fields();
+        if !matches!(op, "AND" | "OR" | "XOR") {
+            return Err(ParseError::at(input, op, "`AND`, `OR` or `XOR`"));
+        }
 
-    // Parse wire values
-    for line in wire_lines {
-        let parts: Vec<&str> = line.split(": ").collect();
-        let wire = parts[0].to_string();
-        let value = parts[1].parse::<i32>().unwrap();
-        wires.insert(wire, value);
+        gates.push(Gate::new(a, op, b, output));
     }
 
+    Ok(Device { wires, gates })
+}
+
+pub fn part1(device: &Device) -> i64 {
+    let mut wires: HashMap<&str, i32> = device
+        .wires
+        .iter()
+        .map(|(wire, value)| (wire.as_str(), *value))
+        .collect();
+
     // Process logic until all conditions are met
     loop {
         let mut good = true;
 
-        for line in logic_lines.clone() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            let w1 = parts[0];
-            let op = parts[1];
-            let w2 = parts[2];
-            let out = parts[4];
-
-            if !wires.contains_key(w1) || !wires.contains_key(w2) {
+        for gate in &device.gates {
+            if !wires.contains_key(gate.a.as_str()) || !wires.contains_key(gate.b.as_str()) {
                 good = false;
                 continue;
             }
 
-            let val1 = *wires.get(w1).unwrap();
-            let val2 = *wires.get(w2).unwrap();
-            let result = match op {
+            let val1 = wires[gate.a.as_str()];
+            let val2 = wires[gate.b.as_str()];
+            let result = match gate.op.as_str() {
                 "AND" => val1 & val2,
                 "OR" => val1 | val2,
                 "XOR" => val1 ^ val2,
                 _ => continue,
             };
 
-            wires.insert(out.to_string(), result);
+            wires.insert(&gate.output, result);
         }
 
         if good {
@@ -50,29 +76,35 @@ pub fn part1(input: &str) -> i64 {
     }
 
     // Collect and process final wire values
-    let mut values: Vec<(String, i32)> = wires
+    let mut values: Vec<(&str, i32)> = wires
         .iter()
         .filter(|(k, _)| k.starts_with('z'))
-        .map(|(k, &v)| (k.clone(), v))
+        .map(|(&k, &v)| (k, v))
         .collect();
 
-    values.sort_by(|a, b| a.0.cmp(&b.0));
+    values.sort_by(|a, b| a.0.cmp(b.0));
 
     let binary_string: String = values.iter().rev().map(|(_, v)| v.to_string()).collect();
 
     i64::from_str_radix(&binary_string, 2).unwrap()
 }
 
-struct Gate<'a> {
-    a: &'a str,
-    op: &'a str,
-    b: &'a str,
-    output: &'a str,
+#[derive(Debug)]
+struct Gate {
+    a: String,
+    op: String,
+    b: String,
+    output: String,
 }
 
-impl<'a> Gate<'a> {
-    fn new(a: &'a str, op: &'a str, b: &'a str, output: &'a str) -> Gate<'a> {
-        Gate { a, op, b, output }
+impl Gate {
+    fn new(a: &str, op: &str, b: &str, output: &str) -> Gate {
+        Gate {
+            a: a.to_string(),
+            op: op.to_string(),
+            b: b.to_string(),
+            output: output.to_string(),
+        }
     }
 
     // Method to check if the gate is direct (either a or b starts with 'x')
@@ -90,97 +122,60 @@ impl<'a> Gate<'a> {
     }
 }
 
-fn part2(input: &str) -> String {
-    let mut sections = input.trim().split("\n\n");
-    let wires_raw = sections.next().unwrap().lines();
-    let gates_raw = sections.next().unwrap().lines();
-
-    // Parse wires
-    let mut wires: HashMap<&str, Option<i32>> = HashMap::new();
-    for line in wires_raw {
-        let mut parts = line.split(": ");
-        let name = parts.next().unwrap();
-        let value = parts.next().unwrap().parse::<i32>().unwrap();
-        wires.insert(name, Some(value));
-    }
-
-    let input_bit_count = wires.len() / 2;
-
-    // Parse gates
-    let mut gates = Vec::new();
-    for line in gates_raw {
-        let mut parts = line.split(" -> ");
-        let inputs = parts.next().unwrap();
-        let output = parts.next().unwrap();
-
-        let mut input_parts = inputs.split(' ');
-        let a = input_parts.next().unwrap();
-        let op = input_parts.next().unwrap();
-        let b = input_parts.next().unwrap();
-
-        gates.push(Gate::new(a, op, b, output));
-
-        if !wires.contains_key(a) {
-            wires.insert(a, None);
-        }
-        if !wires.contains_key(b) {
-            wires.insert(b, None);
-        }
-        if !wires.contains_key(output) {
-            wires.insert(output, None);
-        }
-    }
+pub fn part2(device: &Device) -> String {
+    let gates = &device.gates;
+    let input_bit_count = device.wires.len() / 2;
 
     let mut flags: HashSet<&str> = HashSet::new();
 
-    for gate in &gates {
+    for gate in gates {
         if gate.is_direct() && gate.check_opcode("XOR") {
             let is_first = gate.a == "x00" || gate.b == "x00";
             if is_first {
                 if gate.output != "z00" {
-                    flags.insert(gate.output);
+                    flags.insert(&gate.output);
                 }
                 continue;
             } else if gate.output == "z00" {
-                flags.insert(gate.output);
+                flags.insert(&gate.output);
             }
 
             if gate.is_output() {
-                flags.insert(gate.output);
+                flags.insert(&gate.output);
             }
         }
     }
 
-    for gate in &gates {
+    for gate in gates {
         if gate.check_opcode("XOR") && !gate.is_direct() && !gate.is_output() {
-            flags.insert(gate.output);
+            flags.insert(&gate.output);
         }
     }
 
-    // Output gates check
-    for gate in &gates {
+    // Output gates check: every z is a sum bit, but the last one is the final carry
+    for gate in gates {
         if gate.is_output() {
-            if gate.output == format!("z{:03}", input_bit_count) {
-                if gate.check_opcode("OR") {
-                    flags.insert(gate.output);
+            if gate.output == format!("z{:02}", input_bit_count) {
+                if !gate.check_opcode("OR") {
+                    flags.insert(&gate.output);
                 }
                 continue;
             } else if gate.op != "XOR" {
-                flags.insert(gate.output);
+                flags.insert(&gate.output);
             }
         }
     }
 
     // More complex checks
     let mut check_next = Vec::new();
-    for gate in &gates {
+    for gate in gates {
         if gate.is_direct() && gate.check_opcode("XOR") {
-            if flags.contains(gate.output) || gate.output == "z00" {
+            if flags.contains(gate.output.as_str()) || gate.output == "z00" {
                 continue;
             }
 
             let mut found = false;
-            for check_gate in &gates {
+            for check_gate in gates {
                 if check_gate.check_opcode("XOR") && !check_gate.is_direct() {
                     if check_gate.a == gate.output || check_gate.b == gate.output {
                         found = true;
@@ -191,7 +186,7 @@ fn part2(input: &str) -> String {
 
             if !found {
                 check_next.push(gate);
-                flags.insert(gate.output);
+                flags.insert(&gate.output);
             }
         }
     }
@@ -200,17 +195,17 @@ fn part2(input: &str) -> String {
         let intended_result = format!("z{}", &gate.a[1..]);
         let mut matches = Vec::new();
 
-        for check_gate in &gates {
+        for check_gate in gates {
             if check_gate.check_opcode("XOR") && check_gate.output == intended_result {
                 matches.push(check_gate);
             }
         }
 
         let match_gate = matches[0];
-        let to_check = [match_gate.a, match_gate.b];
+        let to_check = [match_gate.a.as_str(), match_gate.b.as_str()];
 
         let mut or_matches = Vec::new();
-        for check_gate in &gates {
+        for check_gate in gates {
             if check_gate.check_opcode("OR") {
                 if check_gate.output == to_check[0] || check_gate.output == to_check[1] {
                     or_matches.push(check_gate);
@@ -230,39 +225,41 @@ fn part2(input: &str) -> String {
     flags.into_iter().sorted().collect::<Vec<_>>().join(",")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day24;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day24 {
+    type Input = Device;
+    type Params = ();
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day24/example.txt");
+    const DAY: u8 = 24;
 
-        let result = part1(&input);
-        assert_eq!(result, 4, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day24/input.txt");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
+    }
 
-        let result = part1(&input);
-        assert_eq!(result, 55920211035878, "Failed on my input case for part1");
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
+
+    solution_tests!(Day24);
 
     #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day24/input.txt");
-
-        let result = part2(&input);
-        assert_eq!(
-            result, "btb,cmv,mwp,rdg,rmj,z17,z23,z30,z45",
-            "Failed on my input case for part1"
-        );
+    fn test_parse_errors() {
+        let error = parse_input("x00: 1\ny00: 0\n\nx00 AND y00\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 12));
+        assert_eq!(error.expected, "` -> `");
+
+        let error = parse_input("x00: 1\ny00: 0\n\nx00 NAND y00 -> z00\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 5));
     }
 }
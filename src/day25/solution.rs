@@ -1,3 +1,7 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::Solution;
+
 fn get_height(column: &str) -> usize {
     column
         .chars()
@@ -26,7 +30,12 @@ fn can_fit(lock: &[usize], key: &[usize]) -> bool {
     lock.iter().zip(key.iter()).all(|(&l, &k)| l + k <= 5)
 }
 
-pub fn part1(input: &str) -> usize {
+pub struct Schematics {
+    locks: Vec<Vec<usize>>,
+    keys: Vec<Vec<usize>>,
+}
+
+pub fn parse_input(input: &str) -> Result<Schematics, ParseError> {
     let schematics: Vec<&str> = input.trim().split("\n\n").collect();
     let mut locks = Vec::new();
     let mut keys = Vec::new();
@@ -40,34 +49,39 @@ pub fn part1(input: &str) -> usize {
         }
     }
 
-    locks
+    Ok(Schematics { locks, keys })
+}
+
+pub fn part1(schematics: &Schematics) -> usize {
+    schematics
+        .locks
         .iter()
-        .flat_map(|lock| keys.iter().filter(move |key| can_fit(lock, key)))
+        .flat_map(|lock| schematics.keys.iter().filter(move |key| can_fit(lock, key)))
         .count()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day25;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day25 {
+    type Input = Schematics;
+    type Params = ();
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day25/example.txt");
+    const DAY: u8 = 25;
+    const PARTS: u8 = 1;
 
-        let result = part1(&input);
-        assert_eq!(result, 3, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day25/input.txt");
-
-        let result = part1(&input);
-        assert_eq!(result, 2978, "Failed on my input case for part1");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
+
+    solution_tests!(Day25);
+}
@@ -1,3 +1,6 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::Solution;
 use regex::{Captures, Regex};
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -39,40 +42,33 @@ pub fn part2(input: &str) -> i32 {
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day3;
 
-    #[test]
-    fn test_example_case() {
-        let input =
-            fs::read_to_string("input/day3/example.txt").expect("Failed to read input file");
-        let result = part1(&input);
-        assert_eq!(result, 161);
-    }
+impl Solution for Day3 {
+    type Input = String;
+    type Params = ();
 
-    #[test]
-    fn test_my_input_example_case() {
-        let input = fs::read_to_string("input/day3/input.txt").expect("Failed to read input file");
-        let result = part1(&input);
-        assert_eq!(result, 170068701);
+    const DAY: u8 = 3;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_string())
     }
 
-    #[test]
-    fn test_example_case_part_2() {
-        let input =
-            fs::read_to_string("input/day3/example.txt").expect("Failed to read input file");
-        let result = part2(&input);
-        assert_eq!(result, 48);
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
     }
 
-    #[test]
-    fn test_my_input_case_part_2() {
-        let input = fs::read_to_string("input/day3/input.txt").expect("Failed to read input file");
-        let result = part2(&input);
-        assert_eq!(result, 78683433);
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
+
+    solution_tests!(Day3);
 
     #[test]
     fn test_empty_input() {
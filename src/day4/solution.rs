@@ -1,45 +1,33 @@
-use crate::utils;
-use std::collections::HashMap;
-
-fn part1(input: &str) -> i32 {
-    let vec: Vec<Vec<char>> =
-        utils::strings::split_element_by_element_array_input_into_vector(&input);
-
-    let vec1: Vec<String> = get_all_permutations_with_window(&vec, 4);
-    let map: HashMap<String, i32> = utils::maps::group_and_count_by_type(vec1);
-    let xmas_count: i32 = *map.get("XMAS").unwrap_or(&0);
-    let samx_count: i32 = *map.get("SAMX").unwrap_or(&0);
-
-    xmas_count + samx_count
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::Solution;
+use crate::utils::counter::Counter;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+
+pub fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::chars(input)
 }
 
-fn part2(input: &str) -> i32 {
-    let grid: Vec<Vec<char>> =
-        utils::strings::split_element_by_element_array_input_into_vector(&input);
-
-    let mut count = 0;
-    let rows = grid.len();
-    let cols = if rows > 0 { grid[0].len() } else { 0 };
-
-    for row in 1..(rows - 1) {
-        for col in 1..(cols - 1) {
-            if is_xmas_pattern(&grid, row, col) {
-                count += 1;
-            }
-        }
-    }
+pub fn part1(grid: &Grid<char>) -> i32 {
+    let words: Counter<String, i32> = get_all_permutations_with_window(grid, 4)
+        .into_iter()
+        .collect();
 
-    count
+    words.get("XMAS") + words.get("SAMX")
 }
 
-fn is_xmas_pattern(grid: &Vec<Vec<char>>, row: usize, col: usize) -> bool {
-    if row == 0 || col == 0 || row + 1 >= grid.len() || col + 1 >= grid[0].len() {
-        return false;
-    }
+pub fn part2(grid: &Grid<char>) -> i32 {
+    grid.find_all(&'A')
+        .filter(|&position| is_xmas_pattern(grid, position))
+        .count() as i32
+}
 
-    if grid[row][col] != 'A' {
-        return false;
-    }
+fn is_xmas_pattern(grid: &Grid<char>, position: Point<usize>) -> bool {
+    let corner = |x, y| {
+        grid.step(position, Point::new(x, y))
+            .map(|corner| grid[corner])
+    };
 
     let check_pattern = |left_top, left_bottom, right_top, right_bottom| {
         [
@@ -52,93 +40,75 @@ fn is_xmas_pattern(grid: &Vec<Vec<char>>, row: usize, col: usize) -> bool {
         .any(|&x| x)
     };
 
-    let left_top = grid[row - 1][col - 1];
-    let left_bottom = grid[row + 1][col - 1];
-    let right_top = grid[row - 1][col + 1];
-    let right_bottom = grid[row + 1][col + 1];
-
-    check_pattern(left_top, left_bottom, right_top, right_bottom)
-}
-
-pub fn get_all_permutations_with_window(grid: &Vec<Vec<char>>, window_size: usize) -> Vec<String> {
-    let mut permutations = Vec::new();
-    let rows = grid.len();
-    let cols = if rows > 0 { grid[0].len() } else { 0 };
-
-    for row in grid.iter() {
-        for start in 0..=(row.len().saturating_sub(window_size)) {
-            permutations.push(row[start..start + window_size].iter().collect::<String>());
+    match (corner(-1, -1), corner(-1, 1), corner(1, -1), corner(1, 1)) {
+        (Some(left_top), Some(left_bottom), Some(right_top), Some(right_bottom)) => {
+            check_pattern(left_top, left_bottom, right_top, right_bottom)
         }
+        _ => false,
     }
+}
 
-    // Vertical permutations
-    for col in 0..cols {
-        for start in 0..=(rows.saturating_sub(window_size)) {
-            let vertical: String = (start..start + window_size)
-                .map(|row| grid[row][col])
-                .collect();
-            permutations.push(vertical);
-        }
-    }
+pub fn get_all_permutations_with_window(grid: &Grid<char>, window_size: usize) -> Vec<String> {
+    let mut permutations = Vec::new();
 
-    // Diagonal (Top-left to Bottom-right)
-    for start_row in 0..=(rows.saturating_sub(window_size)) {
-        for start_col in 0..=(cols.saturating_sub(window_size)) {
-            let diagonal: String = (0..window_size)
-                .map(|i| grid[start_row + i][start_col + i])
-                .collect();
-            permutations.push(diagonal);
+    // Rows read left to right, and the columns as the rows of the transposed grid
+    for lines in [grid, &grid.transpose()] {
+        for row in lines.rows() {
+            for window in row.windows(window_size) {
+                permutations.push(window.iter().collect::<String>());
+            }
         }
     }
 
-    // Diagonal (Top-right to Bottom-left)
-    for start_row in 0..=(rows.saturating_sub(window_size)) {
-        for start_col in (window_size - 1)..cols {
-            let diagonal: String = (0..window_size)
-                .map(|i| grid[start_row + i][start_col - i])
-                .collect();
-            permutations.push(diagonal);
+    // Diagonals from top-left to bottom-right, and from top-right to bottom-left as the
+    // former of the mirrored grid. A grid narrower or shorter than the window has none
+    for lines in [grid, &grid.flip()] {
+        for start_row in 0..(lines.height() + 1).saturating_sub(window_size) {
+            for start_col in 0..(lines.width() + 1).saturating_sub(window_size) {
+                let diagonal: String = (0..window_size)
+                    .map(|i| lines[Point::new(start_col + i, start_row + i)])
+                    .collect();
+                permutations.push(diagonal);
+            }
         }
     }
 
     permutations
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    #[test]
-    fn test_example_case() {
-        let input =
-            fs::read_to_string("input/day4/example.txt").expect("Failed to read input file");
+pub struct Day4;
 
-        let result = part1(&input);
-        assert_eq!(result, 18); // Example expected result, adjust accordingly
-    }
+impl Solution for Day4 {
+    type Input = Grid<char>;
+    type Params = ();
 
-    #[test]
-    fn test_my_input_example_case() {
-        let input = fs::read_to_string("input/day4/input.txt").expect("Failed to read input file");
+    const DAY: u8 = 4;
 
-        let result = part1(&input);
-        assert_eq!(result, 2569); // Example expected result, adjust accordingly
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_example_case_part_2() {
-        let input =
-            fs::read_to_string("input/day4/example.txt").expect("Failed to read input file");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
+    }
 
-        let result = part2(&input);
-        assert_eq!(result, 9); // Example expected result, adjust accordingly
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
 
-    #[test]
-    fn test_my_input_case_part_2() {
-        let input = fs::read_to_string("input/day4/input.txt").expect("Failed to read input file");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
 
-        let result = part2(&input);
-        assert_eq!(result, 1998);
+    solution_tests!(Day4);
+
+    #[test]
+    fn test_grid_smaller_than_a_word() {
+        let grid = parse_input("MAS\nAXM\nSMA\n").unwrap();
+        assert_eq!(part1(&grid), 0);
+        assert_eq!(part1(&parse_input("X\n").unwrap()), 0);
+        assert_eq!(get_all_permutations_with_window(&grid, 3).len(), 8);
     }
 }
@@ -1,3 +1,6 @@
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::{number, split_once, ParseError};
+use crate::aoc::solution::Solution;
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -7,20 +10,35 @@ enum Order {
     Unknown,
 }
 
-fn parse_input(input: &str) -> (HashMap<i32, HashMap<i32, Order>>, Vec<&str>) {
-    let sections: Vec<&str> = input.split("\n\n").collect();
-    let rules = parse_rules(sections[0]);
-    let data = sections[1].lines().collect();
-    (rules, data)
+#[derive(Debug)]
+pub struct Manual {
+    rules: HashMap<i32, HashMap<i32, Order>>,
+    updates: Vec<Vec<i32>>,
 }
 
-pub fn part1(input: &str) -> i32 {
-    let (rules, data) = parse_input(input);
-    data.iter()
-        .filter_map(|line| {
-            let parts = parse_parts(line);
-            let print = parse_print(&parts);
-            if check_print(&print, &rules) {
+pub fn parse_input(input: &str) -> Result<Manual, ParseError> {
+    let (rules, updates) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::at(
+            input,
+            &input[input.len()..],
+            "a blank line before the updates",
+        )
+    })?;
+    let rules = parse_rules(input, rules)?;
+    let updates = updates
+        .lines()
+        .map(|line| parse_parts(input, line))
+        .collect::<Result<_, _>>()?;
+    Ok(Manual { rules, updates })
+}
+
+pub fn part1(manual: &Manual) -> i32 {
+    manual
+        .updates
+        .iter()
+        .filter_map(|parts| {
+            let print = parse_print(parts);
+            if check_print(&print, &manual.rules) {
                 Some(parts[(parts.len() - 1) / 2])
             } else {
                 None
@@ -29,23 +47,19 @@ pub fn part1(input: &str) -> i32 {
         .sum()
 }
 
-fn parse_parts(line: &str) -> Vec<i32> {
-    line.split(',')
-        .map(|part| part.parse::<i32>().expect("Invalid number in input line"))
-        .collect()
+fn parse_parts(input: &str, line: &str) -> Result<Vec<i32>, ParseError> {
+    line.split(',').map(|part| number(input, part)).collect()
 }
 
-fn solve_part2(data: &str) -> i32 {
-    let (rules, lines) = parse_input(data); // Parse input data into rules and lines
-
-    lines
+pub fn part2(manual: &Manual) -> i32 {
+    manual
+        .updates
         .iter()
-        .map(|line| {
-            let parts: Vec<i32> = line.split(",").map(|part| part.parse().unwrap()).collect();
-            let print = parse_print(&parts);
+        .map(|parts| {
+            let print = parse_print(parts);
 
-            if !check_print(&print, &rules) {
-                let fixed = fix_print(&parts, &rules);
+            if !check_print(&print, &manual.rules) {
+                let fixed = fix_print(parts, &manual.rules);
                 let fixed_parts: Vec<i32> = fixed
                     .as_str()
                     .split(",")
@@ -60,26 +74,22 @@ fn solve_part2(data: &str) -> i32 {
         .sum()
 }
 
-fn parse_rules(data: &str) -> HashMap<i32, HashMap<i32, Order>> {
+fn parse_rules(input: &str, data: &str) -> Result<HashMap<i32, HashMap<i32, Order>>, ParseError> {
     let mut map: HashMap<i32, HashMap<i32, Order>> = HashMap::new();
 
     for line in data.lines() {
-        let split: Vec<&str> = line.split("|").collect();
-        let before: i32 = split[0].parse::<i32>().unwrap();
-        let after: i32 = split[1].parse::<i32>().unwrap();
-
-        map.entry(before)
-            .or_insert(HashMap::new())
-            .insert(after, Order::After);
-        map.entry(after)
-            .or_insert(HashMap::new())
-            .insert(before, Order::Before);
+        let (before, after) = split_once(input, line, "|")?;
+        let before: i32 = number(input, before)?;
+        let after: i32 = number(input, after)?;
+
+        map.entry(before).or_default().insert(after, Order::After);
+        map.entry(after).or_default().insert(before, Order::Before);
     }
 
-    map
+    Ok(map)
 }
 
-fn parse_print(parts: &Vec<i32>) -> HashMap<i32, HashMap<i32, Order>> {
+fn parse_print(parts: &[i32]) -> HashMap<i32, HashMap<i32, Order>> {
     let mut map: HashMap<i32, HashMap<i32, Order>> = HashMap::new();
 
     for (current, number) in parts.iter().enumerate() {
@@ -156,50 +166,67 @@ fn fix_print(nums: &[i32], rules: &HashMap<i32, HashMap<i32, Order>>) -> String
         .join(",")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day5;
 
-    fn load_input(path: &str) -> String {
-        fs::read_to_string(path).expect("Failed to read input file")
+impl Solution for Day5 {
+    type Input = Manual;
+    type Params = ();
+
+    const DAY: u8 = 5;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_example_case() {
-        let input = load_input("input/day5/example.txt");
-        let result = part1(&input);
-        assert_eq!(result, 143);
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
     }
 
-    #[test]
-    fn test_my_input_example_case() {
-        let input = load_input("input/day5/input.txt");
-        let result = part1(&input);
-        assert_eq!(result, 5268);
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
+
+    solution_tests!(Day5);
 
     #[test]
     fn test_multiple_rules() {
         let input = "1|2\n2|3\n\n1,2,3";
-        let result = part1(input);
+        let result = part1(&parse_input(input).unwrap());
         assert_eq!(result, 2);
     }
 
     #[test]
     fn test_empty_input() {
         let input = "\n\n";
-        let result = part1(input);
+        let result = part1(&parse_input(input).unwrap());
         assert_eq!(result, 0);
     }
 
     #[test]
     fn test_invalid_rule() {
         let input = "1|2\n\n2,1";
-        let result = part1(input);
+        let result = part1(&parse_input(input).unwrap());
         assert_eq!(result, 0);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input("1|2\n3\n\n1,3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "`|`");
+
+        let error = parse_input("1|2\n\n1,x").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+
+        assert!(parse_input("1|2\n").is_err());
+    }
+
     #[test]
     fn test_large_number_of_rules() {
         let input = (1..100)
@@ -211,38 +238,21 @@ mod tests {
                 .map(|i| i.to_string())
                 .collect::<Vec<String>>()
                 .join(",");
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         assert_eq!(result, 50);
     }
 
     #[test]
     fn test_single_item() {
         let input = "1|2\n\n1";
-        let result = part1(input);
+        let result = part1(&parse_input(input).unwrap());
         assert_eq!(result, 1);
     }
 
     #[test]
     fn test_order_violation() {
         let input = "1|2\n\n2,1";
-        let result = part1(input);
+        let result = part1(&parse_input(input).unwrap());
         assert_eq!(result, 0);
     }
-
-    #[test]
-    fn test_example_case_part_2() {
-        let input =
-            fs::read_to_string("input/day5/example.txt").expect("Failed to read input file");
-
-        let result = solve_part2(&input);
-        assert_eq!(result, 123);
-    }
-
-    #[test]
-    fn test_my_input_case_part_2() {
-        let input = fs::read_to_string("input/day5/input.txt").expect("Failed to read input file");
-
-        let result = solve_part2(&input);
-        assert_eq!(result, 5799);
-    }
 }
@@ -1,4 +1,7 @@
-struct Input {
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::{number, split_once, ParseError};
+use crate::aoc::solution::Solution;
+pub struct Equation {
     expected: i64,
     values: Vec<i64>,
 }
@@ -19,36 +22,32 @@ impl Operation {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Input> {
+pub fn parse_input(input: &str) -> Result<Vec<Equation>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let mut parts = line.split(':');
-            let expected: i64 = parts.next().unwrap().trim().parse::<i64>().unwrap();
-            let values: Vec<i64> = parts
-                .next()
-                .unwrap()
+            let (expected, values) = split_once(input, line, ":")?;
+            let expected: i64 = number(input, expected.trim())?;
+            let values = values
                 .split_whitespace()
-                .map(|v| v.parse::<i64>().unwrap())
-                .collect();
-            Input { expected, values }
+                .map(|v| number(input, v))
+                .collect::<Result<_, _>>()?;
+            Ok(Equation { expected, values })
         })
         .collect()
 }
 
-pub fn part1(input: &String) -> i64 {
-    let data = parse_input(input);
-
-    <[Input]>::iter(&data)
+pub fn part1(equations: &[Equation]) -> i64 {
+    equations
+        .iter()
         .filter(|x| can_create_value_by_addition_multiplication(x, 0, *x.values.first().unwrap()))
         .map(|x| x.expected)
         .sum()
 }
 
-pub fn part2(input: &String) -> i64 {
-    let data = parse_input(input);
-
-    <[Input]>::iter(&data)
+pub fn part2(equations: &[Equation]) -> i64 {
+    equations
+        .iter()
         .filter(|x| {
             can_create_value_by_addition_multiplication_concatenation(
                 x,
@@ -61,7 +60,7 @@ pub fn part2(input: &String) -> i64 {
 }
 
 fn can_create_value_by_addition_multiplication_concatenation(
-    input: &Input,
+    input: &Equation,
     i: usize,
     current_value: i64,
 ) -> bool {
@@ -80,7 +79,7 @@ fn can_create_value_by_addition_multiplication_concatenation(
 }
 
 fn can_create_value_by_addition_multiplication(
-    input: &Input,
+    input: &Equation,
     i: usize,
     current_value: i64,
 ) -> bool {
@@ -96,51 +95,38 @@ fn can_create_value_by_addition_multiplication(
     })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day7;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day7 {
+    type Input = Vec<Equation>;
+    type Params = ();
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day7/example.txt");
+    const DAY: u8 = 7;
 
-        let result = part1(&input);
-        assert_eq!(result, 3749, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day7/input.txt");
-
-        let result = part1(&input);
-        assert_eq!(result, 882304362421, "Failed on my input case for part1");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
     }
 
-    #[test]
-    fn test_example_case_part2() {
-        let input = load_input("input/day7/example.txt");
-
-        let result = part2(&input);
-        assert_eq!(result, 11387, "Failed on example case for part2");
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
 
-    #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day7/input.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
 
-        let result = part2(&input);
-        assert_eq!(result, 145149066755184, "Failed on my input case for part2");
-    }
+    solution_tests!(Day7);
 
     #[test]
     fn test_parse_input() {
         let input = "10: 1 2 3\n15: 5 5\n20: 10 2\n";
-        let result = parse_input(input);
+        let result = parse_input(input).unwrap();
 
         assert_eq!(result.len(), 3);
         assert_eq!(result[0].expected, 10);
@@ -154,14 +140,14 @@ mod tests {
     #[test]
     fn test_part1() {
         let input = String::from("7: 1 2 3\n6: 2 3\n10: 1 2 3\n");
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         assert_eq!(result, 6, "Failed on part 1 for part1");
     }
 
     #[test]
     fn test_part2() {
         let input = String::from("123: 1 2 3\n26: 2 3\n15: 1 5\n");
-        let result = part2(&input);
+        let result = part2(&parse_input(&input).unwrap());
         assert_eq!(result, 138, "Failed on example case for part2");
     }
 }
@@ -1,26 +1,25 @@
-use crate::utils::strings::split_element_by_element_array_input_into_vector;
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::Solution;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-pub fn part1(input: &str) -> usize {
-    let grid: Vec<Vec<char>> = split_element_by_element_array_input_into_vector(input);
-    let grid_len = grid.len();
-
-    let find_antinodes = |a: (isize, isize), b: (isize, isize)| -> Vec<(isize, isize)> {
-        let (ax, ay) = a;
-        let (bx, by) = b;
-
-        [
-            (ax - (bx - ax), ay - (by - ay)),
-            (bx + (bx - ax), by + (by - ay)),
-        ]
-        .into_iter()
-        .filter(|&(x, y)| in_bounds(x, y, grid_len))
-        .collect()
+pub fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::chars(input)
+}
+
+pub fn part1(grid: &Grid<char>) -> usize {
+    let find_antinodes = |a: Point<isize>, b: Point<isize>| -> Vec<Point<isize>> {
+        [a - (b - a), b + (b - a)]
+            .into_iter()
+            .filter(|&antinode| grid.contains(antinode))
+            .collect()
     };
 
     let mut result = HashSet::new();
-    for positions in digest_chars(&grid).values() {
+    for positions in digest_chars(grid).values() {
         for (a, b) in positions.iter().tuple_combinations() {
             result.extend(find_antinodes(*a, *b));
         }
@@ -29,64 +28,45 @@ pub fn part1(input: &str) -> usize {
     result.len()
 }
 
-fn digest_chars(grid: &Vec<Vec<char>>) -> HashMap<char, Vec<(isize, isize)>> {
-    let mut char_positions: HashMap<char, Vec<(isize, isize)>> = HashMap::new();
+fn digest_chars(grid: &Grid<char>) -> HashMap<char, Vec<Point<isize>>> {
+    let mut char_positions: HashMap<char, Vec<Point<isize>>> = HashMap::new();
 
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &ch) in row.iter().enumerate() {
-            if ch != '.' {
-                char_positions
-                    .entry(ch)
-                    .or_default()
-                    .push((i as isize, j as isize));
-            }
+    for (position, &ch) in grid.iter() {
+        if ch != '.' {
+            char_positions
+                .entry(ch)
+                .or_default()
+                .push(position.signed());
         }
     }
 
     char_positions
 }
 
-fn in_bounds(x: isize, y: isize, n: usize) -> bool {
-    x >= 0 && y >= 0 && x < n as isize && y < n as isize
-}
-
-pub fn part2(input: &String) -> usize {
-    let grid: Vec<Vec<char>> = split_element_by_element_array_input_into_vector(input);
-    let n = grid.len();
-
-    let get_antinodes = |a: (isize, isize), b: (isize, isize)| -> Vec<(isize, isize)> {
-        let (ax, ay) = a;
-        let (bx, by) = b;
-        let (dx, dy) = (bx - ax, by - ay);
+pub fn part2(grid: &Grid<char>) -> usize {
+    let get_antinodes = |a: Point<isize>, b: Point<isize>| -> Vec<Point<isize>> {
+        let delta = b - a;
 
         let mut antinodes = Vec::new();
 
-        let mut i = 0;
-        while let Some((nx, ny)) = ax.checked_sub(dx * i).zip(ay.checked_sub(dy * i)) {
-            if in_bounds(nx, ny, n) {
-                antinodes.push((nx, ny));
-            } else {
-                break;
-            }
-            i += 1;
+        let mut antinode = a;
+        while grid.contains(antinode) {
+            antinodes.push(antinode);
+            antinode -= delta;
         }
 
-        i = 0;
-        while let Some((nx, ny)) = bx.checked_add(dx * i).zip(by.checked_add(dy * i)) {
-            if in_bounds(nx, ny, n) {
-                antinodes.push((nx, ny));
-            } else {
-                break;
-            }
-            i += 1;
+        antinode = b;
+        while grid.contains(antinode) {
+            antinodes.push(antinode);
+            antinode += delta;
         }
 
         antinodes
     };
 
-    let mut antinodes: HashSet<(isize, isize)> = HashSet::new();
+    let mut antinodes: HashSet<Point<isize>> = HashSet::new();
 
-    for (_freq, locs) in &digest_chars(&grid) {
+    for locs in digest_chars(grid).values() {
         for (a, b) in locs.iter().tuple_combinations() {
             for antinode in get_antinodes(*a, *b) {
                 antinodes.insert(antinode);
@@ -97,85 +77,80 @@ pub fn part2(input: &String) -> usize {
     antinodes.len()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day8;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day8 {
+    type Input = Grid<char>;
+    type Params = ();
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day8/example.txt");
+    const DAY: u8 = 8;
 
-        let result = part1(&input);
-        assert_eq!(result, 14, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day8/input.txt");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
+    }
 
-        let result = part1(&input);
-        assert_eq!(result, 390, "Failed on my input case for part1");
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
 
-    #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day8/input.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
 
-        let result = part2(&input);
-        assert_eq!(result, 1246, "Failed on my input case for part2");
-    }
+    solution_tests!(Day8);
 
     #[test]
     fn test_part1_no_antinodes() {
         let input = "....\n....\n....\n....\n".to_string();
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         assert_eq!(result, 0); // No characters to form antinodes
     }
 
     #[test]
     fn test_part2_no_antinodes() {
         let input = "....\n....\n....\n....\n".to_string();
-        let result = part2(&input);
+        let result = part2(&parse_input(&input).unwrap());
         assert_eq!(result, 0); // No characters to form antinodes
     }
 
     #[test]
     fn test_part1_empty_grid() {
         let input = String::from("");
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         assert_eq!(result, 0);
     }
 
     #[test]
     fn test_part1_no_non_dot() {
         let input = String::from("...\n...\n...");
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         assert_eq!(result, 0);
     }
 
     #[test]
     fn test_part1_single_character() {
         let input = String::from(".A.\n...\n...");
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         assert_eq!(result, 0); // Only one 'A', no pairs to generate antinodes
     }
 
     #[test]
     fn test_part1_two_characters_no_antinode() {
         let input = String::from("A..\n...\n..A");
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         assert_eq!(result, 0); // Antinodes are out of bounds
     }
 
     #[test]
     fn test_part1_two_characters_with_antinode() {
         let input = String::from("..A..\n.....\nA....");
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         // Positions: (0,2) and (2,0)
         // Antinodes: (0 - (2-0), 2 - (0-2)) = (-2,4) out of bounds
         // (2 + (2-0), 0 + (0-2)) = (4,-2) out of bounds
@@ -186,7 +161,7 @@ mod tests {
     #[test]
     fn test_part1_two_characters_with_in_bounds_antinode() {
         let input = String::from("A..A\n....\n....\nA..A");
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         // Positions: (0,0), (0,3), (3,0), (3,3)
         // Pairs:
         // (0,0)-(0,3): cx=0 - (0-0)=0, cy=0 - (3-0)= -3 (out)
@@ -203,28 +178,28 @@ mod tests {
     #[test]
     fn test_part2_empty_grid() {
         let input = String::from("");
-        let result = part2(&input);
+        let result = part2(&parse_input(&input).unwrap());
         assert_eq!(result, 0);
     }
 
     #[test]
     fn test_part2_no_non_dot() {
         let input = String::from("...\n...\n...");
-        let result = part2(&input);
+        let result = part2(&parse_input(&input).unwrap());
         assert_eq!(result, 0);
     }
 
     #[test]
     fn test_part2_single_character() {
         let input = String::from(".A.\n...\n...");
-        let result = part2(&input);
+        let result = part2(&parse_input(&input).unwrap());
         assert_eq!(result, 0); // Only one 'A', no pairs to generate antinodes
     }
 
     #[test]
     fn test_part1_different_characters() {
         let input = String::from("A.B\n.C.\nB.A");
-        let result = part1(&input);
+        let result = part1(&parse_input(&input).unwrap());
         // Characters: 'A', 'B', 'C'
         // 'A': (0,0), (2,2)
         // 'B': (0,2), (2,0)
@@ -1,11 +1,27 @@
-pub fn part1(input: &str) -> usize {
-    let filesystem = |disk_map: &str| -> Vec<Option<usize>> {
+use crate::aoc::answer::Answer;
+use crate::aoc::parse::ParseError;
+use crate::aoc::solution::Solution;
+use crate::utils::intervals::IntervalSet;
+
+pub fn parse_input(input: &str) -> Result<Vec<usize>, ParseError> {
+    let disk_map = input.trim();
+    disk_map
+        .char_indices()
+        .map(|(index, x)| {
+            x.to_digit(10)
+                .map(|digit| digit as usize)
+                .ok_or_else(|| ParseError::at(input, &disk_map[index..], "a digit"))
+        })
+        .collect()
+}
+
+pub fn part1(disk_map: &[usize]) -> usize {
+    let filesystem = |disk_map: &[usize]| -> Vec<Option<usize>> {
         let mut blocks = Vec::new();
         let mut is_file = true;
         let mut id = 0;
 
-        for x in disk_map.chars() {
-            let count = x.to_digit(10).unwrap() as usize;
+        for &count in disk_map {
             if is_file {
                 blocks.extend(vec![Some(id); count]);
                 id += 1;
@@ -17,7 +33,7 @@ pub fn part1(input: &str) -> usize {
         }
 
         blocks
-    }(input);
+    }(disk_map);
 
     let moved_filesystem = |mut arr: Vec<Option<usize>>| -> Vec<Option<usize>> {
         let mut first_free = 0;
@@ -56,129 +72,68 @@ fn checksum(arr: &[Option<usize>]) -> usize {
         .sum()
 }
 
-fn part2(input: &str) -> usize {
-    fn make_filesystem(
-        diskmap: &str,
-        size: &mut Vec<usize>,
-        loc: &mut Vec<usize>,
-    ) -> Vec<Option<usize>> {
-        let mut blocks = Vec::new();
-        let mut is_file = true;
-        let mut id = 0;
-
-        for x in diskmap.chars() {
-            let x = x.to_digit(10).unwrap() as usize;
-            if is_file {
-                loc[id] = blocks.len();
-                size[id] = x;
-                blocks.extend(vec![Some(id); x]);
-                id += 1;
-                is_file = false;
-            } else {
-                blocks.extend(vec![None; x]);
-                is_file = true;
-            }
+/// Moves every whole file, highest id first, to the lowest free span to its left that fits
+/// it. The free spans are indexed by length, so finding one never rescans the disk.
+pub fn part2(disk_map: &[usize]) -> usize {
+    // Start and size of every file, by id
+    let mut files = Vec::new();
+    let mut free = IntervalSet::new();
+    let mut position = 0;
+
+    for (index, &length) in disk_map.iter().enumerate() {
+        if index % 2 == 0 {
+            files.push((position, length));
+        } else {
+            free.insert(position..position + length);
         }
-
-        blocks
+        position += length;
     }
 
-    fn move_blocks(
-        arr: &mut Vec<Option<usize>>,
-        size: &[usize],
-        loc: &mut Vec<usize>,
-    ) -> Vec<Option<usize>> {
-        // Find the last file with a positive size
-        let mut big = 0;
-        while size[big] > 0 {
-            big += 1;
+    for (start, length) in files.iter_mut().rev() {
+        if *length == 0 {
+            continue;
         }
-        big -= 1;
-
-        for to_move in (0..=big).rev() {
-            // Find the first free space that works
-            let mut free_space = 0;
-            let mut first_free = 0;
-
-            while first_free < loc[to_move] && free_space < size[to_move] {
-                first_free += free_space;
-                free_space = 0;
-
-                while first_free < arr.len() && arr[first_free].is_some() {
-                    first_free += 1;
-                }
-                while first_free + free_space < arr.len() && arr[first_free + free_space].is_none()
-                {
-                    free_space += 1;
-                }
-            }
 
-            if first_free >= loc[to_move] {
-                continue;
-            }
-
-            for idx in first_free..(first_free + size[to_move]) {
-                arr[idx] = Some(to_move);
-            }
-            for idx in loc[to_move]..(loc[to_move] + size[to_move]) {
-                arr[idx] = None;
-            }
-            loc[to_move] = first_free;
+        // The space a file leaves is never reused, as every file still to move is to its
+        // left, and leaving it out keeps the spans no longer than 9 blocks
+        if let Some(space) = free.first_fit(*length).filter(|space| space.start < *start) {
+            free.remove(space.start..space.start + *length);
+            *start = space.start;
         }
-
-        arr.clone()
     }
 
-    let line = input.trim();
-
-    let mut size = vec![0; line.len()];
-    let mut loc = vec![0; line.len()];
-
-    let mut filesystem = make_filesystem(line, &mut size, &mut loc);
-    let moved_filesystem = move_blocks(&mut filesystem, &size, &mut loc);
-    let result = checksum(&moved_filesystem);
-
-    result
+    files
+        .iter()
+        .enumerate()
+        .map(|(id, &(start, length))| (start..start + length).sum::<usize>() * id)
+        .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
+pub struct Day9;
 
-    fn load_input(file_path: &str) -> String {
-        fs::read_to_string(file_path).expect(&format!("Failed to read input file: {}", file_path))
-    }
+impl Solution for Day9 {
+    type Input = Vec<usize>;
+    type Params = ();
 
-    #[test]
-    fn test_example_case_part1() {
-        let input = load_input("input/day9/example.txt");
+    const DAY: u8 = 9;
 
-        let result = part1(&input);
-        assert_eq!(result, 1928, "Failed on example case for part1");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_my_input_case_part1() {
-        let input = load_input("input/day9/input.txt");
-
-        let result = part1(&input);
-        assert_eq!(result, 6353658451014, "Failed on my input case for part1");
+    fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part1(input).into()
     }
 
-    #[test]
-    fn test_example_input_case_part2() {
-        let input = load_input("input/day9/example.txt");
-
-        let result = part2(&input);
-        assert_eq!(result, 2858, "Failed on example case for part2");
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Answer {
+        part2(input).into()
     }
+}
 
-    #[test]
-    fn test_my_input_case_part2() {
-        let input = load_input("input/day9/input.txt");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::testing::solution_tests;
 
-        let result = part2(&input);
-        assert_eq!(result, 6382582136592, "Failed on my input case for part2");
-    }
+    solution_tests!(Day9);
 }
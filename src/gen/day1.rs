@@ -0,0 +1,28 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// Two columns of five-digit location ids; `size` is the number of lines. Many ids of the
+/// right column are taken from the left one, so the similarity score isn't always zero.
+pub struct Day1;
+
+impl Generator for Day1 {
+    type Options = ();
+
+    const DAY: u8 = 1;
+    const SIZE: usize = 1000;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let left: Vec<i64> = (0..size).map(|_| rng.i64(10_000..100_000)).collect();
+
+        let mut input = String::new();
+        for &id in &left {
+            let right = if rng.chance(0.3) {
+                *rng.choose(&left)
+            } else {
+                rng.i64(10_000..100_000)
+            };
+            input += &format!("{}   {}\n", id, right);
+        }
+        input
+    }
+}
@@ -0,0 +1,33 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+
+/// A square topographic map; `size` is its side. Random heights alone hardly ever climb
+/// from 0 to 9, so uphill trails are laid over them as well.
+pub struct Day10;
+
+impl Generator for Day10 {
+    type Options = ();
+
+    const DAY: u8 = 10;
+    const SIZE: usize = 50;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut grid = Grid::filled(size, size, 0);
+        for position in grid.positions() {
+            grid[position] = rng.below(10);
+        }
+
+        for _ in 0..size * size / 20 {
+            let mut position = Point::new(rng.usize(0..size), rng.usize(0..size));
+            grid[position] = 0;
+            for height in 1..10 {
+                let next: Vec<Point<usize>> = grid.neighbours4(position).collect();
+                position = *rng.choose(&next);
+                grid[position] = height;
+            }
+        }
+        grid.to_string()
+    }
+}
@@ -0,0 +1,23 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// A line of stones engraved with numbers below 10000; `size` is the number of stones.
+pub struct Day11;
+
+impl Generator for Day11 {
+    type Options = ();
+
+    const DAY: u8 = 11;
+    const SIZE: usize = 8;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let stones: Vec<String> = (0..size)
+            .map(|_| match rng.below(4) {
+                0 => rng.i64(0..10),
+                _ => rng.i64(0..10_000),
+            })
+            .map(|stone| stone.to_string())
+            .collect();
+        stones.join(" ")
+    }
+}
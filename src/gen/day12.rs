@@ -0,0 +1,39 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+
+/// A square garden; `size` is its side. Each plot takes the plant of the nearest of a few
+/// random seeds, which makes regions of many shapes, and some single plots are replanted
+/// to leave holes in them.
+pub struct Day12;
+
+impl Generator for Day12 {
+    type Options = ();
+
+    const DAY: u8 = 12;
+    const SIZE: usize = 140;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let seeds: Vec<(Point<usize>, char)> = (0..(size * size / 25).max(1))
+            .map(|_| {
+                let position = Point::new(rng.usize(0..size), rng.usize(0..size));
+                (position, (b'A' + rng.below(26) as u8) as char)
+            })
+            .collect();
+
+        let mut grid = Grid::filled(size, size, 'A');
+        for position in grid.positions() {
+            let &(_, plant) = seeds
+                .iter()
+                .min_by_key(|(seed, _)| seed.manhattan(position))
+                .unwrap();
+            grid[position] = if rng.chance(0.02) {
+                (b'A' + rng.below(26) as u8) as char
+            } else {
+                plant
+            };
+        }
+        grid.to_string()
+    }
+}
@@ -0,0 +1,42 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// Claw machines with buttons moving 10 to 99 along each axis; `size` is the number of
+/// machines. Most prizes can be won with at most 100 presses of each button, the others
+/// are anywhere.
+pub struct Day13;
+
+impl Generator for Day13 {
+    type Options = ();
+
+    const DAY: u8 = 13;
+    const SIZE: usize = 320;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut machines = Vec::new();
+        while machines.len() < size {
+            let (ax, ay, bx, by) = (
+                rng.i64(10..100),
+                rng.i64(10..100),
+                rng.i64(10..100),
+                rng.i64(10..100),
+            );
+            // Parallel buttons would leave many ways to win
+            if ax * by == ay * bx {
+                continue;
+            }
+
+            let (px, py) = if rng.chance(0.6) {
+                let (a, b) = (rng.i64(0..101), rng.i64(0..101));
+                (a * ax + b * bx, a * ay + b * by)
+            } else {
+                (rng.i64(1000..20_000), rng.i64(1000..20_000))
+            };
+            machines.push(format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                ax, ay, bx, by, px, py
+            ));
+        }
+        machines.join("\n")
+    }
+}
@@ -0,0 +1,60 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::day14::solution::Params;
+use crate::utils::geom::Point;
+
+/// Robots in a room of the default size; `size` is the number of robots. At a random
+/// second more than half of them gather in a framed box, the picture part 2 looks for.
+pub struct Day14;
+
+/// Width and height of the framed picture.
+const FRAME: (i64, i64) = (31, 33);
+
+impl Generator for Day14 {
+    type Options = ();
+
+    const DAY: u8 = 14;
+    const SIZE: usize = 500;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let params = Params::default();
+        let (width, height) = (params.width as i64, params.height as i64);
+        let seconds = rng.i64(1..width * height);
+
+        // The frame first, then anywhere inside it
+        let corner = Point::new(rng.i64(0..width - FRAME.0), rng.i64(0..height - FRAME.1));
+        let mut picture: Vec<Point<i64>> = (0..FRAME.0)
+            .flat_map(|x| [Point::new(x, 0), Point::new(x, FRAME.1 - 1)])
+            .chain((1..FRAME.1 - 1).flat_map(|y| [Point::new(0, y), Point::new(FRAME.0 - 1, y)]))
+            .collect();
+        let mut inside: Vec<Point<i64>> = (1..FRAME.0 - 1)
+            .flat_map(|x| (1..FRAME.1 - 1).map(move |y| Point::new(x, y)))
+            .collect();
+        rng.shuffle(&mut inside);
+        picture.extend(inside);
+        picture.truncate(size * 3 / 5);
+
+        let mut input = String::new();
+        for index in 0..size {
+            let target = match picture.get(index) {
+                Some(&offset) => corner + offset,
+                None => Point::new(rng.i64(0..width), rng.i64(0..height)),
+            };
+
+            let velocity = Point::new(rng.i64(1..width), rng.i64(1..height));
+            let velocity = Point::new(
+                velocity.x - if rng.chance(0.5) { width } else { 0 },
+                velocity.y - if rng.chance(0.5) { height } else { 0 },
+            );
+            let start = Point::new(
+                (target.x - velocity.x * seconds).rem_euclid(width),
+                (target.y - velocity.y * seconds).rem_euclid(height),
+            );
+            input += &format!(
+                "p={},{} v={},{}\n",
+                start.x, start.y, velocity.x, velocity.y
+            );
+        }
+        input
+    }
+}
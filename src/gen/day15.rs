@@ -0,0 +1,38 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+
+/// A walled-in square warehouse with boxes, inner walls and the robot, followed by its
+/// moves in lines of 70; `size` is the side, and the robot makes 400 moves per unit of it.
+pub struct Day15;
+
+impl Generator for Day15 {
+    type Options = ();
+
+    const DAY: u8 = 15;
+    const SIZE: usize = 50;
+    const MIN_SIZE: usize = 3;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut grid = Grid::filled(size, size, '#');
+        for y in 1..size - 1 {
+            for x in 1..size - 1 {
+                grid[Point::new(x, y)] = match rng.below(10) {
+                    0 => '#',
+                    1 | 2 => 'O',
+                    _ => '.',
+                };
+            }
+        }
+        let robot = Point::new(rng.usize(1..size - 1), rng.usize(1..size - 1));
+        grid[robot] = '@';
+
+        let moves: Vec<u8> = (0..400 * size).map(|_| *rng.choose(b"^>v<")).collect();
+        let lines: Vec<&str> = moves
+            .chunks(70)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect();
+        format!("{}\n\n{}", grid, lines.join("\n"))
+    }
+}
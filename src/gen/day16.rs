@@ -0,0 +1,63 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+
+/// A square maze with the start in the bottom left corner and the end in the top right;
+/// `size` is its side, less one when even. A perfect maze has a single way through, so
+/// some walls are knocked out to make several paths tie or nearly tie.
+pub struct Day16;
+
+impl Generator for Day16 {
+    type Options = ();
+
+    const DAY: u8 = 16;
+    const SIZE: usize = 141;
+    const MIN_SIZE: usize = 5;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut grid = maze(rng, size);
+        let side = grid.width();
+
+        for _ in 0..side * side / 50 {
+            // A wall between two cells of the maze, across or along
+            let (x, y) = (rng.usize(1..side - 1), rng.usize(1..side - 1));
+            if (x % 2 == 1) != (y % 2 == 1) {
+                grid[Point::new(x, y)] = '.';
+            }
+        }
+
+        grid[Point::new(1, side - 2)] = 'S';
+        grid[Point::new(side - 2, 1)] = 'E';
+        grid.to_string()
+    }
+}
+
+/// A perfect maze carved by a randomised depth-first search: a wall all around, passages
+/// on the cells with odd coordinates, and exactly one path between any two of them. The
+/// side is `size`, less one when even.
+pub(super) fn maze(rng: &mut Rng, size: usize) -> Grid<char> {
+    let side = if size % 2 == 0 { size - 1 } else { size };
+    let mut grid = Grid::filled(side, side, '#');
+
+    let start = Point::new(1, 1);
+    grid[start] = '.';
+    let mut stack = vec![start];
+    while let Some(&cell) = stack.last() {
+        let unvisited: Vec<Point<usize>> = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| cell.offset(Point::new(dx, dy)))
+            .filter(|next| next.x < side - 1 && next.y < side - 1 && grid[*next] == '#')
+            .collect();
+
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let next = *rng.choose(&unvisited);
+        grid[Point::new((cell.x + next.x) / 2, (cell.y + next.y) / 2)] = '.';
+        grid[next] = '.';
+        stack.push(next);
+    }
+    grid
+}
@@ -0,0 +1,43 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::day17::solution::{parse_input, part2};
+
+/// A program shaped like the real ones, a single loop that prints one number per three
+/// bits of register A; `size` is the number of numbers it prints. Programs that can't
+/// print themselves are drawn again, since part 2 needs one that can.
+pub struct Day17;
+
+impl Generator for Day17 {
+    type Options = ();
+
+    const DAY: u8 = 17;
+    const SIZE: usize = 16;
+    // Register A must hold three bits per number printed
+    const MAX_SIZE: usize = 20;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        loop {
+            // B = A % 8, B ^= x, C = A >> B, then B ^= y, B ^= C and A >>= 3 in any order
+            let mut middle = [[1, rng.below(8)], [4, rng.below(8)], [0, 3]];
+            rng.shuffle(&mut middle);
+            let program: Vec<String> = [[2, 4], [1, rng.below(8)], [7, 5]]
+                .iter()
+                .chain(&middle)
+                .chain(&[[5, 5], [3, 0]])
+                .flatten()
+                .map(u64::to_string)
+                .collect();
+
+            let lowest = 1 << (3 * size - 3);
+            let register_a = lowest + rng.below((lowest << 3) - lowest);
+            let input = format!(
+                "Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+                register_a,
+                program.join(",")
+            );
+            if part2(&parse_input(&input).unwrap()).is_some() {
+                return input;
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::day18::solution::Params;
+use crate::utils::geom::Point;
+
+/// Bytes falling on distinct cells of a memory space of the default size, never on its
+/// corners; `size` is the number of bytes. Enough of them cut the corners apart.
+pub struct Day18;
+
+const SIDE: usize = 71;
+
+impl Generator for Day18 {
+    type Options = ();
+
+    const DAY: u8 = 18;
+    const SIZE: usize = 3450;
+    const MAX_SIZE: usize = SIDE * SIDE - 2;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        debug_assert_eq!(Params::default().size, SIDE);
+
+        let mut cells: Vec<Point<usize>> = (0..SIDE)
+            .flat_map(|y| (0..SIDE).map(move |x| Point::new(x, y)))
+            .filter(|&cell| cell != Point::new(0, 0) && cell != Point::new(SIDE - 1, SIDE - 1))
+            .collect();
+        rng.shuffle(&mut cells);
+
+        cells[..size]
+            .iter()
+            .map(|cell| format!("{},{}\n", cell.x, cell.y))
+            .collect()
+    }
+}
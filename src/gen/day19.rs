@@ -0,0 +1,88 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::aoc::params::params;
+use crate::utils::hash::FxHashSet;
+
+params! {
+    pub struct Options {
+        /// Stripe colours to use, some of `wubrg`.
+        alphabet: String = "wubrg".to_string(),
+        /// Towels available, if there are that many different ones of up to eight stripes.
+        towels: usize = 450,
+    }
+}
+
+/// Towels and the designs to make with them; `size` is the number of designs. One colour
+/// has no single-stripe towel and ends no towel, and some designs put together from towels
+/// get that colour at the end, so not all of them can be made.
+pub struct Day19;
+
+impl Generator for Day19 {
+    type Options = Options;
+
+    const DAY: u8 = 19;
+    const SIZE: usize = 400;
+
+    fn check(options: &Options) -> Result<(), String> {
+        let mut seen = FxHashSet::default();
+        match options
+            .alphabet
+            .chars()
+            .all(|ch| "wubrg".contains(ch) && seen.insert(ch))
+        {
+            true if !seen.is_empty() => Ok(()),
+            _ => Err(format!(
+                "the alphabet must be distinct colours of `wubrg`, not `{}`",
+                options.alphabet
+            )),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize, options: &Options) -> String {
+        let colours: Vec<char> = options.alphabet.chars().collect();
+        let stripes = |rng: &mut Rng, length: usize| -> String {
+            (0..length).map(|_| *rng.choose(&colours)).collect()
+        };
+
+        // No towel ends in the missing colour, so no design ending in it can be made
+        let mut towels: Vec<String> = colours.iter().map(char::to_string).collect();
+        let missing = match colours.len() {
+            1 => None,
+            _ => Some(towels.remove(rng.usize(0..colours.len()))),
+        };
+        let mut seen: FxHashSet<String> = towels.iter().cloned().collect();
+        for _ in 0..options.towels * 20 {
+            if towels.len() >= options.towels {
+                break;
+            }
+            let length = rng.usize(2..9);
+            let towel = stripes(rng, length);
+            if missing
+                .as_ref()
+                .is_some_and(|colour| towel.ends_with(colour.as_str()))
+            {
+                continue;
+            }
+            if seen.insert(towel.clone()) {
+                towels.push(towel);
+            }
+        }
+        towels.truncate(options.towels.max(1));
+        rng.shuffle(&mut towels);
+
+        let mut designs = Vec::new();
+        for _ in 0..size {
+            let length = rng.usize(20..51);
+            let mut design = String::new();
+            while design.len() < length {
+                design += rng.choose(&towels).as_str();
+            }
+            if let Some(colour) = missing.as_ref().filter(|_| rng.chance(0.3)) {
+                design.replace_range(design.len() - 1.., colour);
+            }
+            designs.push(design);
+        }
+
+        format!("{}\n\n{}", towels.join(", "), designs.join("\n"))
+    }
+}
@@ -0,0 +1,35 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// Reports of five to eight levels; `size` is the number of reports. Most start out safe
+/// and some then get one or two levels broken, so both parts count different reports.
+pub struct Day2;
+
+impl Generator for Day2 {
+    type Options = ();
+
+    const DAY: u8 = 2;
+    const SIZE: usize = 1000;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let length = rng.usize(5..9);
+            let sign = if rng.chance(0.5) { 1 } else { -1 };
+            let mut levels = vec![rng.i64(20..80)];
+            for _ in 1..length {
+                levels.push(levels.last().unwrap() + sign * rng.i64(1..4));
+            }
+
+            for _ in 0..rng.usize(0..3) {
+                let index = rng.usize(0..length);
+                levels[index] += rng.i64(-4..5);
+            }
+
+            let line: Vec<String> = levels.iter().map(i64::to_string).collect();
+            input += &line.join(" ");
+            input.push('\n');
+        }
+        input
+    }
+}
@@ -0,0 +1,40 @@
+use super::day16::maze;
+use super::rng::Rng;
+use super::Generator;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+use crate::utils::search::bfs;
+
+/// A racetrack winding from the bottom left corner to the top right: the only path between
+/// them through a perfect maze, with every other passage walled up. `size` is the side,
+/// less one when even.
+pub struct Day20;
+
+impl Generator for Day20 {
+    type Options = ();
+
+    const DAY: u8 = 20;
+    const SIZE: usize = 141;
+    const MIN_SIZE: usize = 5;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let grid = maze(rng, size);
+        let side = grid.width();
+        let (start, end) = (Point::new(1, side - 2), Point::new(side - 2, 1));
+
+        let open = |&position: &Point<usize>| {
+            grid.neighbours4(position)
+                .filter(|&next| grid[next] != '#')
+                .collect::<Vec<_>>()
+        };
+        let track = bfs([start], open).path(&end).unwrap();
+
+        let mut grid = Grid::filled(side, side, '#');
+        for &position in &track {
+            grid[position] = '.';
+        }
+        grid[start] = 'S';
+        grid[end] = 'E';
+        grid.to_string()
+    }
+}
@@ -0,0 +1,18 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// Door codes of three digits and an `A`; `size` is the number of codes.
+pub struct Day21;
+
+impl Generator for Day21 {
+    type Options = ();
+
+    const DAY: u8 = 21;
+    const SIZE: usize = 5;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        (0..size)
+            .map(|_| format!("{:03}A\n", rng.below(1000)))
+            .collect()
+    }
+}
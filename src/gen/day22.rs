@@ -0,0 +1,18 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// The initial secret numbers of the buyers, below 2^24; `size` is the number of buyers.
+pub struct Day22;
+
+impl Generator for Day22 {
+    type Options = ();
+
+    const DAY: u8 = 22;
+    const SIZE: usize = 2000;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        (0..size)
+            .map(|_| format!("{}\n", rng.i64(1..1 << 24)))
+            .collect()
+    }
+}
@@ -0,0 +1,80 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::aoc::params::params;
+use crate::utils::hash::FxHashSet;
+
+params! {
+    pub struct Options {
+        /// Computers of the planted LAN party, which is the largest one.
+        clique: usize = 13,
+    }
+}
+
+/// A network of computers with two-letter names; `size` is the number of computers, up to
+/// every name there is. Besides the planted clique the links are random, and no computer
+/// has more links than the clique has computers, as part 2 tries every subset of the
+/// links of a computer.
+pub struct Day23;
+
+impl Generator for Day23 {
+    type Options = Options;
+
+    const DAY: u8 = 23;
+    const SIZE: usize = 520;
+    const MAX_SIZE: usize = 26 * 26;
+
+    fn check(options: &Options) -> Result<(), String> {
+        match options.clique {
+            2..=16 => Ok(()),
+            clique => Err(format!(
+                "the clique must have 2 to 16 computers, not {}",
+                clique
+            )),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize, options: &Options) -> String {
+        let mut names: Vec<String> = (b'a'..=b'z')
+            .flat_map(|first| (b'a'..=b'z').map(move |second| [first, second]))
+            .map(|name| String::from_utf8(name.to_vec()).unwrap())
+            .collect();
+        rng.shuffle(&mut names);
+        names.truncate(size);
+
+        let clique = options.clique.min(size);
+        let mut links = FxHashSet::default();
+        let mut degrees = vec![0; size];
+        for a in 0..clique {
+            for b in a + 1..clique {
+                links.insert((a, b));
+                degrees[a] += 1;
+                degrees[b] += 1;
+            }
+        }
+
+        // Random links are unlikely to make even a clique of five
+        let most = options.clique.max(4);
+        for _ in 0..size * most {
+            let (a, b) = (rng.usize(0..size), rng.usize(0..size));
+            if a != b
+                && degrees[a] < most
+                && degrees[b] < most
+                && links.insert((a.min(b), a.max(b)))
+            {
+                degrees[a] += 1;
+                degrees[b] += 1;
+            }
+        }
+
+        let mut links: Vec<(usize, usize)> = links.into_iter().collect();
+        links.sort();
+        rng.shuffle(&mut links);
+        links
+            .iter()
+            .map(|&(a, b)| match rng.chance(0.5) {
+                true => format!("{}-{}\n", names[a], names[b]),
+                false => format!("{}-{}\n", names[b], names[a]),
+            })
+            .collect()
+    }
+}
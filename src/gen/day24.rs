@@ -0,0 +1,167 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::aoc::params::params;
+use crate::utils::hash::FxHashSet;
+
+params! {
+    pub struct Options {
+        /// Pairs of gates whose output wires are swapped.
+        swaps: usize = 4,
+    }
+}
+
+/// A ripple-carry adder of two numbers, gate by gate, with some pairs of output wires
+/// swapped; `size` is the number of bits of each number. Small adders have room for fewer
+/// swaps than asked for.
+pub struct Day24;
+
+/// Inputs, operation and output wire of a gate.
+type Gate = [String; 4];
+
+impl Generator for Day24 {
+    type Options = Options;
+
+    const DAY: u8 = 24;
+    const SIZE: usize = 45;
+    const MIN_SIZE: usize = 2;
+    // The output is read as a 64-bit number and named with two digits
+    const MAX_SIZE: usize = 62;
+
+    fn check(options: &Options) -> Result<(), String> {
+        match options.swaps {
+            0..=16 => Ok(()),
+            swaps => Err(format!("at most 16 pairs can be swapped, not {}", swaps)),
+        }
+    }
+
+    fn generate(rng: &mut Rng, size: usize, options: &Options) -> String {
+        let mut wires = Vec::new();
+        for axis in ["x", "y"] {
+            for bit in 0..size {
+                wires.push(format!("{}{:02}: {}", axis, bit, rng.below(2)));
+            }
+        }
+
+        let (mut gates, _) = miswired(rng, size, options.swaps);
+        rng.shuffle(&mut gates);
+        let gates: Vec<String> = gates
+            .iter()
+            .map(|[a, op, b, output]| format!("{} {} {} -> {}", a, op, b, output))
+            .collect();
+        format!("{}\n\n{}", wires.join("\n"), gates.join("\n"))
+    }
+}
+
+/// The gates of the adder with up to `swaps` pairs of outputs swapped, and the wires
+/// swapped. Like in the real puzzle each pair is within the gates of a single bit, never
+/// the first two bits, and no two pairs are in neighbouring bits.
+fn miswired(rng: &mut Rng, bits: usize, swaps: usize) -> (Vec<Gate>, Vec<String>) {
+    let mut gates = adder(rng, bits);
+
+    let mut candidates: Vec<usize> = (2..bits).collect();
+    rng.shuffle(&mut candidates);
+    let mut chosen: Vec<usize> = Vec::new();
+    for bit in candidates {
+        if chosen.len() < swaps && chosen.iter().all(|other| other.abs_diff(bit) > 1) {
+            chosen.push(bit);
+        }
+    }
+
+    let mut wires = Vec::new();
+    for bit in chosen {
+        // Bit 0 has two gates and every other bit five: the sum of the inputs, their AND, the
+        // output bit, the carry's AND and the carry out. Other pairs either feed a gate its
+        // own output or, for the two ANDs, change nothing
+        let cell = 2 + 5 * (bit - 1);
+        let (first, second) = *rng.choose(&[(0, 1), (1, 2), (2, 3), (2, 4)]);
+        let (first, second) = (cell + first, cell + second);
+        let output = gates[first][3].clone();
+        gates[first][3] = std::mem::replace(&mut gates[second][3], output);
+        wires.extend([gates[first][3].clone(), gates[second][3].clone()]);
+    }
+
+    wires.sort();
+    (gates, wires)
+}
+
+/// The gates adding `x` and `y` into `z`, with random names for the inner wires.
+fn adder(rng: &mut Rng, bits: usize) -> Vec<Gate> {
+    let mut used = FxHashSet::default();
+    let mut name = || loop {
+        let name: String = (0..3)
+            .map(|_| (b'a' + rng.below(23) as u8) as char)
+            .collect();
+        if used.insert(name.clone()) {
+            break name;
+        }
+    };
+    let gate = |a: &str, op: &str, b: &str, output: &str| -> Gate {
+        [a, op, b, output].map(str::to_string)
+    };
+
+    let mut gates = vec![gate("x00", "XOR", "y00", "z00")];
+    let mut carry = name();
+    gates.push(gate("x00", "AND", "y00", &carry));
+    for bit in 1..bits {
+        let (x, y, z) = (
+            format!("x{:02}", bit),
+            format!("y{:02}", bit),
+            format!("z{:02}", bit),
+        );
+        let (sum, both, carried) = (name(), name(), name());
+        gates.push(gate(&x, "XOR", &y, &sum));
+        gates.push(gate(&x, "AND", &y, &both));
+        gates.push(gate(&sum, "XOR", &carry, &z));
+        gates.push(gate(&sum, "AND", &carry, &carried));
+
+        carry = match bit + 1 == bits {
+            true => format!("z{:02}", bits),
+            false => name(),
+        };
+        gates.push(gate(&both, "OR", &carried, &carry));
+    }
+    gates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day24::solution::{parse_input, part1, part2};
+
+    fn input(gates: &[Gate], x: i64, y: i64, bits: usize) -> String {
+        let mut lines = Vec::new();
+        for (axis, value) in [("x", x), ("y", y)] {
+            for bit in 0..bits {
+                lines.push(format!("{}{:02}: {}", axis, bit, value >> bit & 1));
+            }
+        }
+        lines.push(String::new());
+        for [a, op, b, output] in gates {
+            lines.push(format!("{} {} {} -> {}", a, op, b, output));
+        }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_adder_adds() {
+        let mut rng = Rng::new(1);
+        let (gates, wires) = miswired(&mut rng, 10, 0);
+        assert!(wires.is_empty());
+
+        let device = parse_input(&input(&gates, 700, 555, 10)).unwrap();
+        assert_eq!(part1(&device), 1255);
+        assert_eq!(part2(&device), "");
+    }
+
+    #[test]
+    fn test_planted_swaps_are_found() {
+        for seed in 0..20 {
+            let mut rng = Rng::new(seed);
+            let (gates, wires) = miswired(&mut rng, 45, 4);
+            assert_eq!(wires.len(), 8);
+
+            let device = parse_input(&input(&gates, 1, 2, 45)).unwrap();
+            assert_eq!(part2(&device), wires.join(","), "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// Lock and key schematics five pins wide, with pins up to five high; `size` is the number
+/// of schematics.
+pub struct Day25;
+
+impl Generator for Day25 {
+    type Options = ();
+
+    const DAY: u8 = 25;
+    const SIZE: usize = 500;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut schematics = Vec::new();
+        for _ in 0..size {
+            let heights: Vec<usize> = (0..5).map(|_| rng.usize(0..6)).collect();
+            // Locks hang from the top row and keys stand on the bottom one
+            let mut rows: Vec<String> = (0..7)
+                .map(|row| {
+                    heights
+                        .iter()
+                        .map(|&height| if row <= height { '#' } else { '.' })
+                        .collect()
+                })
+                .collect();
+            if rng.chance(0.5) {
+                rows.reverse();
+            }
+            schematics.push(rows.join("\n"));
+        }
+        schematics.join("\n\n")
+    }
+}
@@ -0,0 +1,36 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// Corrupted memory; `size` is the number of instructions, valid or not, among the noise.
+/// Besides `mul(a,b)` there are broken multiplications and `do()` and `don't()` switches.
+pub struct Day3;
+
+const NOISE: &[u8] = b"mul(),'don't?!@#$%^&*[]{}<>:;+-_ 0123456789what()select";
+
+impl Generator for Day3 {
+    type Options = ();
+
+    const DAY: u8 = 3;
+    const SIZE: usize = 700;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            for _ in 0..rng.usize(0..12) {
+                input.push(*rng.choose(NOISE) as char);
+            }
+
+            let (a, b) = (rng.i64(1..1000), rng.i64(1..1000));
+            let instruction = match rng.below(10) {
+                0 => "do()".to_string(),
+                1 => "don't()".to_string(),
+                2 => format!("mul({}, {})", a, b),
+                3 => format!("mul[{},{}]", a, b),
+                4 => format!("mul({},{}", a, b),
+                _ => format!("mul({},{})", a, b),
+            };
+            input += &instruction;
+        }
+        input
+    }
+}
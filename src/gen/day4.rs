@@ -0,0 +1,21 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::utils::grid::Grid;
+
+/// A square word search of the letters of `XMAS`; `size` is its side.
+pub struct Day4;
+
+impl Generator for Day4 {
+    type Options = ();
+
+    const DAY: u8 = 4;
+    const SIZE: usize = 140;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut grid = Grid::filled(size, size, 'X');
+        for position in grid.positions() {
+            grid[position] = *rng.choose(&['X', 'M', 'A', 'S']);
+        }
+        grid.to_string()
+    }
+}
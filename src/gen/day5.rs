@@ -0,0 +1,46 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// Page ordering rules and updates; `size` is the number of updates. The pages follow one
+/// hidden order with a rule for every pair, and about half the updates are shuffled.
+pub struct Day5;
+
+const PAGES: usize = 49;
+
+impl Generator for Day5 {
+    type Options = ();
+
+    const DAY: u8 = 5;
+    const SIZE: usize = 200;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut order: Vec<usize> = (10..100).collect();
+        rng.shuffle(&mut order);
+        order.truncate(PAGES);
+
+        let mut rules = Vec::new();
+        for (index, before) in order.iter().enumerate() {
+            for after in &order[index + 1..] {
+                rules.push(format!("{}|{}", before, after));
+            }
+        }
+        rng.shuffle(&mut rules);
+
+        let mut updates = Vec::new();
+        for _ in 0..size {
+            // Odd lengths, so every update has a middle page
+            let length = 2 * rng.usize(2..12) + 1;
+            let mut ranks: Vec<usize> = (0..PAGES).collect();
+            rng.shuffle(&mut ranks);
+            ranks.truncate(length);
+            if rng.chance(0.5) {
+                ranks.sort();
+            }
+
+            let pages: Vec<String> = ranks.iter().map(|&rank| order[rank].to_string()).collect();
+            updates.push(pages.join(","));
+        }
+
+        format!("{}\n\n{}", rules.join("\n"), updates.join("\n"))
+    }
+}
@@ -0,0 +1,51 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::utils::bitset::BitSet;
+use crate::utils::geom::{Direction, Point};
+use crate::utils::grid::Grid;
+
+/// A square lab with scattered obstructions and the guard facing up; `size` is its side.
+/// Maps where the guard walks in a loop are drawn again, as part 1 needs the guard to leave.
+pub struct Day6;
+
+impl Generator for Day6 {
+    type Options = ();
+
+    const DAY: u8 = 6;
+    const SIZE: usize = 130;
+    const MIN_SIZE: usize = 2;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        loop {
+            let mut grid = Grid::filled(size, size, '.');
+            for position in grid.positions() {
+                if rng.chance(0.05) {
+                    grid[position] = '#';
+                }
+            }
+            // Somewhere in the middle, so the walk isn't over at once
+            let middle = size / 4..size - size / 4;
+            let guard = Point::new(rng.usize(middle.clone()), rng.usize(middle));
+            grid[guard] = '^';
+
+            if leaves(&grid, guard) {
+                return grid.to_string();
+            }
+        }
+    }
+}
+
+/// Whether the guard starting at `guard` walks off the map rather than in a loop.
+fn leaves(grid: &Grid<char>, guard: Point<usize>) -> bool {
+    let mut seen = BitSet::new(grid.width() * grid.height() * 4);
+    let (mut position, mut direction) = (guard, Direction::North);
+
+    while seen.insert(grid.index_of(position).unwrap() * 4 + direction as usize) {
+        match grid.step(position, direction.delta()) {
+            None => return true,
+            Some(next) if grid[next] == '#' => direction = direction.turn_right(),
+            Some(next) => position = next,
+        }
+    }
+    false
+}
@@ -0,0 +1,38 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// Calibration equations of two to eight numbers; `size` is the number of equations. Each
+/// test value is made with random operators, including concatenation, and some are then
+/// nudged so no operators make them.
+pub struct Day7;
+
+impl Generator for Day7 {
+    type Options = ();
+
+    const DAY: u8 = 7;
+    const SIZE: usize = 850;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let values: Vec<i64> = (0..rng.usize(2..9)).map(|_| rng.i64(1..100)).collect();
+
+            // At most sixteen digits, whatever the operators, so nothing overflows
+            let mut target = values[0];
+            for &value in &values[1..] {
+                target = match rng.below(3) {
+                    0 => target + value,
+                    1 => target * value,
+                    _ => format!("{}{}", target, value).parse().unwrap(),
+                };
+            }
+            if rng.chance(0.3) {
+                target += rng.i64(1..10);
+            }
+
+            let values: Vec<String> = values.iter().map(i64::to_string).collect();
+            input += &format!("{}: {}\n", target, values.join(" "));
+        }
+        input
+    }
+}
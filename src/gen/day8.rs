@@ -0,0 +1,29 @@
+use super::rng::Rng;
+use super::Generator;
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+
+/// A square city map of antennas; `size` is its side. There are about four antennas of each
+/// frequency, a letter or a digit, on one cell in twelve.
+pub struct Day8;
+
+const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl Generator for Day8 {
+    type Options = ();
+
+    const DAY: u8 = 8;
+    const SIZE: usize = 50;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let antennas = size * size / 12;
+        let frequencies = &FREQUENCIES[..(antennas / 4).clamp(1, FREQUENCIES.len())];
+
+        let mut grid = Grid::filled(size, size, '.');
+        for _ in 0..antennas {
+            let position = Point::new(rng.usize(0..size), rng.usize(0..size));
+            grid[position] = *rng.choose(frequencies) as char;
+        }
+        grid.to_string()
+    }
+}
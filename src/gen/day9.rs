@@ -0,0 +1,28 @@
+use super::rng::Rng;
+use super::Generator;
+
+/// A disk map of files one to nine blocks long with gaps of up to nine; `size` is the
+/// number of files. At least one gap is free space, which compacting the disk needs.
+pub struct Day9;
+
+impl Generator for Day9 {
+    type Options = ();
+
+    const DAY: u8 = 9;
+    const SIZE: usize = 10_000;
+    const MIN_SIZE: usize = 2;
+
+    fn generate(rng: &mut Rng, size: usize, _options: &()) -> String {
+        let mut digits: Vec<u64> = (0..2 * size - 1)
+            .map(|index| match index % 2 {
+                0 => 1 + rng.below(9),
+                _ => rng.below(10),
+            })
+            .collect();
+        if digits.iter().skip(1).step_by(2).all(|&gap| gap == 0) {
+            digits[2 * rng.usize(0..size - 1) + 1] = 1 + rng.below(9);
+        }
+
+        digits.iter().map(u64::to_string).collect()
+    }
+}
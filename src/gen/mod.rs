@@ -0,0 +1,229 @@
+// Random puzzle inputs for stress tests: every day can generate a valid input of any size
+// from a seed, so the same seed always gives the same input.
+mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day2;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+pub(crate) mod rng;
+
+use crate::aoc::params::Params;
+use rng::Rng;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Generates inputs of a single day. What the size counts depends on the day, e.g. lines of
+/// input or the side of a grid, and is documented on each generator.
+pub trait Generator {
+    /// Knobs of the generated puzzle besides its size, or `()` for none.
+    type Options: Params;
+
+    const DAY: u8;
+    /// Size used when none is given, close to that of a real input.
+    const SIZE: usize;
+    /// Smallest size the day can make a valid input of.
+    const MIN_SIZE: usize = 1;
+    /// Largest size the day can make a valid input of.
+    const MAX_SIZE: usize = usize::MAX;
+
+    /// Rejects options the puzzle can't be generated with.
+    fn check(_options: &Self::Options) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn generate(rng: &mut Rng, size: usize, options: &Self::Options) -> String;
+}
+
+/// Object-safe view of a [`Generator`], for the registry below.
+pub trait DynGenerator: Sync {
+    fn day(&self) -> u8;
+
+    fn default_size(&self) -> usize;
+
+    /// Every size the day can make a valid input of.
+    fn sizes(&self) -> RangeInclusive<usize>;
+
+    /// The input of `size` generated from `seed`, with the options in `overrides` applied
+    /// to their defaults. Always ends with a newline, like a downloaded input.
+    fn generate(
+        &self,
+        seed: u64,
+        size: usize,
+        overrides: &BTreeMap<String, String>,
+    ) -> Result<String, String>;
+}
+
+impl<G: Generator + Sync> DynGenerator for G {
+    fn day(&self) -> u8 {
+        G::DAY
+    }
+
+    fn default_size(&self) -> usize {
+        G::SIZE
+    }
+
+    fn sizes(&self) -> RangeInclusive<usize> {
+        G::MIN_SIZE..=G::MAX_SIZE
+    }
+
+    fn generate(
+        &self,
+        seed: u64,
+        size: usize,
+        overrides: &BTreeMap<String, String>,
+    ) -> Result<String, String> {
+        if !self.sizes().contains(&size) {
+            return Err(match G::MAX_SIZE {
+                usize::MAX => format!("day {}: the size must be at least {}", G::DAY, G::MIN_SIZE),
+                max => format!(
+                    "day {}: the size must be between {} and {}",
+                    G::DAY,
+                    G::MIN_SIZE,
+                    max
+                ),
+            });
+        }
+        let options = G::Options::with_overrides(overrides)
+            .and_then(|options| G::check(&options).map(|_| options))
+            .map_err(|err| format!("day {}: {}", G::DAY, err))?;
+
+        let mut input = G::generate(&mut Rng::new(seed), size, &options);
+        if !input.ends_with('\n') {
+            input.push('\n');
+        }
+        Ok(input)
+    }
+}
+
+/// A generator for every day of the calendar, in order.
+pub static GENERATORS: &[&dyn DynGenerator] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+pub fn get(day: u8) -> Option<&'static dyn DynGenerator> {
+    GENERATORS
+        .iter()
+        .find(|generator| generator.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::registry;
+
+    /// Sizes small enough for every part to be solved quickly in a debug build.
+    fn small_size(day: u8) -> usize {
+        match day {
+            4 | 6 | 8 | 10 | 12 | 15 => 12,
+            16 | 20 => 15,
+            17 => 8,
+            18 => 300,
+            24 => 8,
+            _ => 20,
+        }
+    }
+
+    #[test]
+    fn test_registry_is_ordered_by_day() {
+        for (index, generator) in GENERATORS.iter().enumerate() {
+            assert_eq!(generator.day() as usize, index + 1);
+        }
+        assert_eq!(get(9).map(|generator| generator.day()), Some(9));
+        assert!(get(26).is_none());
+    }
+
+    #[test]
+    fn test_generated_inputs_parse_and_solve() {
+        for generator in GENERATORS {
+            let day = generator.day();
+            let solution = registry::get(day).unwrap();
+            let params = solution.params(&BTreeMap::new()).unwrap();
+
+            for seed in 0..3 {
+                let input = generator
+                    .generate(seed, small_size(day), &BTreeMap::new())
+                    .unwrap();
+                let parsed = solution
+                    .parse(&input)
+                    .unwrap_or_else(|err| panic!("seed {}:\n{}", seed, err.render(&input)));
+
+                for part in 1..=solution.parts() {
+                    solution.solve(parsed.as_ref(), params.as_ref(), part);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_input() {
+        for generator in GENERATORS {
+            let size = small_size(generator.day());
+            let first = generator.generate(5, size, &BTreeMap::new()).unwrap();
+            assert_eq!(
+                first,
+                generator.generate(5, size, &BTreeMap::new()).unwrap()
+            );
+            assert_ne!(
+                first,
+                generator.generate(6, size, &BTreeMap::new()).unwrap(),
+                "day {}",
+                generator.day()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_sizes_and_options() {
+        let overrides = BTreeMap::from([("colours".to_string(), "3".to_string())]);
+        assert!(get(19).unwrap().generate(1, 10, &overrides).is_err());
+        let alphabet = BTreeMap::from([("alphabet".to_string(), "wx".to_string())]);
+        assert!(get(19).unwrap().generate(1, 10, &alphabet).is_err());
+        assert!(get(9).unwrap().generate(1, 0, &BTreeMap::new()).is_err());
+        assert!(get(1).unwrap().generate(1, 1, &overrides).is_err());
+    }
+}
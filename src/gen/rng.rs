@@ -0,0 +1,103 @@
+use std::ops::Range;
+
+/// A small xorshift64* generator: fast, seedable and the same on every platform, which is
+/// all generated puzzle inputs need. Not for anything that must be unpredictable.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// A generator whose whole sequence is determined by `seed`. The seed is scrambled
+    /// first, so nearby seeds give unrelated sequences and a zero seed works too.
+    pub fn new(seed: u64) -> Rng {
+        // One round of splitmix64, which never maps to zero from a xorshift's point of view
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        Rng { state: z.max(1) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// A value in `0..n`, for a positive `n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot pick from an empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn usize(&mut self, range: Range<usize>) -> usize {
+        assert!(!range.is_empty(), "cannot pick from {:?}", range);
+        range.start + self.below((range.end - range.start) as u64) as usize
+    }
+
+    pub fn i64(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "cannot pick from {:?}", range);
+        range.start + self.below(range.end.abs_diff(range.start)) as i64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.usize(0..items.len())]
+    }
+
+    /// Puts `items` in a uniformly random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.usize(0..i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let mut c = Rng::new(8);
+
+        let first: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(first, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(first, (0..5).map(|_| c.next_u64()).collect::<Vec<_>>());
+        assert_ne!(Rng::new(0).next_u64(), 0);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let value = rng.i64(-3..3);
+            assert!((-3..3).contains(&value));
+            seen[(value + 3) as usize] = true;
+            assert!((10..12).contains(&rng.usize(10..12)));
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let mut rng = Rng::new(3);
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+}
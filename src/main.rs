@@ -1,3 +1,4 @@
+mod aoc;
 mod day1;
 mod day10;
 mod day11;
@@ -23,8 +24,18 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
+mod gen;
+mod runner;
 mod utils;
 
+#[global_allocator]
+static ALLOCATOR: aoc::memory::CountingAllocator = aoc::memory::CountingAllocator;
+
 fn main() {
-    println!("Hello, AdventOfCode2024!");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if let Err(err) = runner::run(&args) {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
 }
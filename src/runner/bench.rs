@@ -0,0 +1,430 @@
+use super::{parse, parse_day, read_input};
+use crate::aoc::inputs;
+use crate::aoc::memory::{self, format_bytes, AllocStats};
+use crate::aoc::registry;
+use crate::aoc::solution::DynSolution;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub const USAGE: &str =
+    "usage: advent-of-code-2024 bench [DAY...] [--reps N] [--example | --name NAME] \
+                         [--baseline PATH] [--save] [--threshold PCT] [--alloc]";
+
+const DEFAULT_REPS: usize = 5;
+const DEFAULT_BASELINE: &str = "bench/baseline.txt";
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+struct Options {
+    days: Vec<u8>,
+    reps: usize,
+    input: String,
+    baseline: String,
+    save: bool,
+    threshold: f64,
+    alloc: bool,
+}
+
+/// Min, median and max of a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "at least one sample is required");
+        samples.sort();
+
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// One measured phase of a day: `parse`, `part1` or `part2`.
+pub struct Measurement {
+    pub day: u8,
+    /// Name of the input measured, e.g. `input` or `example`.
+    pub input: String,
+    pub phase: String,
+    pub stats: Stats,
+    /// Allocations of one extra, untimed run, when requested with `--alloc`.
+    pub allocs: Option<AllocStats>,
+}
+
+impl Measurement {
+    fn key(&self) -> String {
+        format!("day{}.{}.{}", self.day, self.input, self.phase)
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = parse_args(args)?;
+    let baseline = load_baseline(&options.baseline)?;
+
+    let mut measurements = Vec::new();
+    for &day in &options.days {
+        let solution = registry::get(day).ok_or_else(|| format!("unknown day: {}", day))?;
+        let text = inputs::read(day, &options.input)?;
+        let params = solution.params(&inputs::params(day, &options.input)?)?;
+        measurements.extend(measure_day(
+            solution,
+            &options.input,
+            &text,
+            params.as_ref(),
+            options.reps,
+            options.alloc,
+        )?);
+    }
+
+    let regressions = report(&measurements, &baseline, options.threshold);
+
+    if options.save {
+        save_baseline(&options.baseline, &measurements)?;
+        println!("Saved baseline to {}", options.baseline);
+    }
+
+    if regressions > 0 {
+        return Err(format!(
+            "{} phase(s) regressed by more than {}% against {}",
+            regressions, options.threshold, options.baseline
+        ));
+    }
+
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        days: Vec::new(),
+        reps: DEFAULT_REPS,
+        input: "input".to_string(),
+        baseline: DEFAULT_BASELINE.to_string(),
+        save: false,
+        threshold: DEFAULT_THRESHOLD,
+        alloc: false,
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--example" => options.input = inputs::EXAMPLE.to_string(),
+            "--name" => options.input = flag_value(&mut iter, "--name")?.to_string(),
+            "--save" => options.save = true,
+            "--alloc" => options.alloc = true,
+            "--reps" => {
+                options.reps = flag_value(&mut iter, "--reps")?
+                    .parse()
+                    .ok()
+                    .filter(|&reps| reps > 0)
+                    .ok_or_else(|| format!("--reps expects a positive number\n{}", USAGE))?;
+            }
+            "--baseline" => options.baseline = flag_value(&mut iter, "--baseline")?.to_string(),
+            "--threshold" => {
+                options.threshold = flag_value(&mut iter, "--threshold")?
+                    .parse()
+                    .map_err(|_| format!("--threshold expects a percentage\n{}", USAGE))?;
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown option: {}\n{}", flag, USAGE))
+            }
+            day => options.days.push(parse_day(day)?),
+        }
+    }
+
+    if options.days.is_empty() {
+        options.days = registry::SOLUTIONS.iter().map(|s| s.day()).collect();
+    }
+
+    Ok(options)
+}
+
+fn flag_value<'a>(
+    iter: &mut impl Iterator<Item = &'a String>,
+    flag: &str,
+) -> Result<&'a str, String> {
+    iter.next()
+        .map(String::as_str)
+        .ok_or_else(|| format!("{} expects a value\n{}", flag, USAGE))
+}
+
+/// Times the parser and every part of a day `reps` times each. With `alloc`, every phase
+/// runs once more to count its allocations, so the counting does not skew the timings.
+pub fn measure_day(
+    solution: &dyn DynSolution,
+    name: &str,
+    input: &str,
+    params: &dyn Any,
+    reps: usize,
+    alloc: bool,
+) -> Result<Vec<Measurement>, String> {
+    let day = solution.day();
+    let mut measurements = Vec::new();
+
+    let mut samples = Vec::with_capacity(reps);
+    let mut parsed = None;
+    for _ in 0..reps {
+        let start = Instant::now();
+        let result = black_box(parse(solution, input)?);
+        samples.push(start.elapsed());
+        parsed = Some(result);
+    }
+    let parsed = parsed.expect("at least one repetition");
+    measurements.push(Measurement {
+        day,
+        input: name.to_string(),
+        phase: "parse".to_string(),
+        stats: Stats::from_samples(samples),
+        allocs: alloc.then(|| memory::measure(|| black_box(solution.parse(input))).1),
+    });
+
+    for part in 1..=solution.parts() {
+        let mut samples = Vec::with_capacity(reps);
+        for _ in 0..reps {
+            let start = Instant::now();
+            black_box(solution.solve(parsed.as_ref(), params, part));
+            samples.push(start.elapsed());
+        }
+        measurements.push(Measurement {
+            day,
+            input: name.to_string(),
+            phase: format!("part{}", part),
+            stats: Stats::from_samples(samples),
+            allocs: alloc.then(|| {
+                memory::measure(|| black_box(solution.solve(parsed.as_ref(), params, part))).1
+            }),
+        });
+    }
+
+    Ok(measurements)
+}
+
+/// Prints the timing table and returns how many phases regressed against the baseline.
+fn report(
+    measurements: &[Measurement],
+    baseline: &BTreeMap<String, u128>,
+    threshold: f64,
+) -> usize {
+    let mut regressions = 0;
+    let alloc_columns = |allocs: Option<AllocStats>| match allocs {
+        Some(allocs) => format!(
+            " {:>10} {:>10} {:>10}",
+            allocs.allocations,
+            format_bytes(allocs.bytes),
+            format_bytes(allocs.peak)
+        ),
+        None => String::new(),
+    };
+    let header = if measurements.iter().any(|m| m.allocs.is_some()) {
+        format!(" {:>10} {:>10} {:>10}", "allocs", "allocated", "peak")
+    } else {
+        String::new()
+    };
+
+    println!(
+        "{:>3}  {:<6} {:>10} {:>10} {:>10}{}  baseline",
+        "day", "phase", "min", "median", "max", header
+    );
+    for measurement in measurements {
+        let comparison = match baseline.get(&measurement.key()) {
+            Some(&previous) => {
+                let change = relative_change(previous, measurement.stats.median.as_nanos());
+                if change > threshold {
+                    regressions += 1;
+                    format!("{:+.1}%  REGRESSION", change)
+                } else {
+                    format!("{:+.1}%", change)
+                }
+            }
+            None => "-".to_string(),
+        };
+
+        println!(
+            "{:>3}  {:<6} {:>10} {:>10} {:>10}{}  {}",
+            measurement.day,
+            measurement.phase,
+            format_duration(measurement.stats.min),
+            format_duration(measurement.stats.median),
+            format_duration(measurement.stats.max),
+            alloc_columns(measurement.allocs),
+            comparison
+        );
+    }
+
+    regressions
+}
+
+fn relative_change(previous: u128, current: u128) -> f64 {
+    if previous == 0 {
+        return 0.0;
+    }
+    (current as f64 - previous as f64) / previous as f64 * 100.0
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    if nanos < 1_000.0 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000.0 {
+        format!("{:.1}µs", nanos / 1_000.0)
+    } else if nanos < 1_000_000_000.0 {
+        format!("{:.2}ms", nanos / 1_000_000.0)
+    } else {
+        format!("{:.2}s", nanos / 1_000_000_000.0)
+    }
+}
+
+/// Reads a baseline of `dayN.input.phase = median_nanos` lines; a missing file is an empty baseline.
+fn load_baseline(path: &str) -> Result<BTreeMap<String, u128>, String> {
+    if !Path::new(path).exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    parse_baseline(&read_input(path)?)
+}
+
+fn parse_baseline(text: &str) -> Result<BTreeMap<String, u128>, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("invalid baseline line: {}", line))?;
+            let nanos = value
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid baseline timing: {}", line))?;
+            Ok((key.trim().to_string(), nanos))
+        })
+        .collect()
+}
+
+fn save_baseline(path: &str, measurements: &[Measurement]) -> Result<(), String> {
+    let mut baseline = load_baseline(path)?;
+    for measurement in measurements {
+        baseline.insert(measurement.key(), measurement.stats.median.as_nanos());
+    }
+
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {}", parent.display(), err))?;
+    }
+
+    let mut text = String::from("# median timings in nanoseconds\n");
+    for (key, nanos) in &baseline {
+        text.push_str(&format!("{} = {}\n", key, nanos));
+    }
+
+    fs::write(path, text).map_err(|err| format!("failed to write {}: {}", path, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(millis(&[5, 1, 3, 9, 4]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!(stats.max, Duration::from_millis(9));
+    }
+
+    #[test]
+    fn test_parse_baseline() {
+        let baseline =
+            parse_baseline("# comment\nday6.input.part2 = 1500\n\nday1.example.parse=20\n")
+                .unwrap();
+        assert_eq!(baseline.get("day6.input.part2"), Some(&1500));
+        assert_eq!(baseline.get("day1.example.parse"), Some(&20));
+        assert!(parse_baseline("day1.input.parse 20").is_err());
+    }
+
+    #[test]
+    fn test_relative_change() {
+        assert_eq!(relative_change(100, 125), 25.0);
+        assert_eq!(relative_change(200, 100), -50.0);
+        assert_eq!(relative_change(0, 100), 0.0);
+    }
+
+    #[test]
+    fn test_report_counts_regressions() {
+        let measurement = |phase: &str, ms| Measurement {
+            day: 3,
+            input: "input".to_string(),
+            phase: phase.to_string(),
+            stats: Stats::from_samples(millis(&[ms])),
+            allocs: None,
+        };
+        let measurements = vec![measurement("part1", 12), measurement("part2", 10)];
+        let baseline = BTreeMap::from([
+            ("day3.input.part1".to_string(), 10_000_000),
+            ("day3.input.part2".to_string(), 10_000_000),
+        ]);
+
+        assert_eq!(report(&measurements, &baseline, 10.0), 1);
+        assert_eq!(report(&measurements, &baseline, 25.0), 0);
+    }
+
+    #[test]
+    fn test_measure_day_times_every_phase() {
+        let input = inputs::read(1, "example").unwrap();
+        let measurements =
+            measure_day(registry::get(1).unwrap(), "example", &input, &(), 3, false).unwrap();
+        let phases: Vec<&str> = measurements.iter().map(|m| m.phase.as_str()).collect();
+        assert_eq!(phases, vec!["parse", "part1", "part2"]);
+        assert!(measurements.iter().all(|m| m.allocs.is_none()));
+    }
+
+    #[test]
+    fn test_measure_day_counts_allocations() {
+        let input = inputs::read(1, "example").unwrap();
+        let measurements =
+            measure_day(registry::get(1).unwrap(), "example", &input, &(), 1, true).unwrap();
+        let parse = measurements[0].allocs.unwrap();
+        assert!(parse.allocations > 0 && parse.bytes > 0);
+    }
+
+    #[test]
+    fn test_baseline_keeps_inputs_apart() {
+        let path = env::temp_dir().join(format!("aoc-bench-{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        let measurement = |input: &str, ms| Measurement {
+            day: 1,
+            input: input.to_string(),
+            phase: "part1".to_string(),
+            stats: Stats::from_samples(millis(&[ms])),
+            allocs: None,
+        };
+
+        save_baseline(path, &[measurement("input", 20)]).unwrap();
+        save_baseline(path, &[measurement("example", 1)]).unwrap();
+        let baseline = load_baseline(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(baseline.get("day1.input.part1"), Some(&20_000_000));
+        assert_eq!(baseline.get("day1.example.part1"), Some(&1_000_000));
+        assert_eq!(report(&[measurement("input", 20)], &baseline, 10.0), 0);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512ns");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50ms");
+        assert_eq!(format_duration(Duration::from_secs(2)), "2.00s");
+    }
+}
@@ -0,0 +1,404 @@
+use super::parse_day;
+use super::report::{panic_message, quietly};
+use crate::aoc::registry;
+use crate::aoc::solution::{DynSolution, Side};
+use crate::gen::rng::Rng;
+use crate::gen::{self, DynGenerator};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+pub const USAGE: &str =
+    "usage: advent-of-code-2024 diff [DAY...] [--cases N] [--size N] [--seed S]";
+
+/// How many generated inputs each pair is run on, and which.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cases {
+    count: u64,
+    /// Seed of the first input; the others follow it.
+    seed: u64,
+    /// Largest size of an input, the generator's default when not given.
+    max_size: Option<usize>,
+}
+
+/// What one side of a pair gave on an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Answer(String),
+    Panic(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{}", answer),
+            Outcome::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+/// A generated input the two sides of a pair disagree on, shrunk as far as it would go.
+#[derive(Debug)]
+struct Mismatch {
+    day: u8,
+    pair: &'static str,
+    seed: u64,
+    size: usize,
+    input: String,
+    reference: Outcome,
+    optimised: Outcome,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Day {} {}: mismatch on seed {}, size {}",
+            self.day, self.pair, self.seed, self.size
+        )?;
+        writeln!(f, "  reference: {}", self.reference)?;
+        writeln!(f, "  optimised: {}", self.optimised)?;
+        write!(f, "  minimised input:\n{}", self.input)
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut days = Vec::new();
+    let mut cases = Cases {
+        count: 50,
+        seed: 0,
+        max_size: None,
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut number = |flag: &str| {
+            iter.next()
+                .and_then(|value| value.parse::<u64>().ok())
+                .ok_or_else(|| format!("{} expects a number\n{}", flag, USAGE))
+        };
+        match arg.as_str() {
+            "--cases" => cases.count = number("--cases")?,
+            "--seed" => cases.seed = number("--seed")?,
+            "--size" => cases.max_size = Some(number("--size")? as usize),
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown option: {}\n{}", flag, USAGE))
+            }
+            day => days.push(parse_day(day)?),
+        }
+    }
+    if days.is_empty() {
+        days = registry::SOLUTIONS
+            .iter()
+            .filter(|solution| !solution.pair_names().is_empty())
+            .map(|solution| solution.day())
+            .collect();
+    }
+
+    let mut mismatches = 0;
+    quietly(|| {
+        for &day in &days {
+            let solution = registry::get(day).ok_or_else(|| format!("unknown day: {}", day))?;
+            let generator =
+                gen::get(day).ok_or_else(|| format!("day {} has no input generator", day))?;
+
+            for (index, name) in solution.pair_names().into_iter().enumerate() {
+                match first_mismatch(solution, generator, index, cases)? {
+                    Some(mismatch) => {
+                        print!("{}", mismatch);
+                        mismatches += 1;
+                    }
+                    None => println!("Day {} {}: {} cases agree", day, name, cases.count),
+                }
+            }
+        }
+        Ok::<_, String>(())
+    })?;
+
+    if mismatches > 0 {
+        return Err(format!("{} pair(s) disagree", mismatches));
+    }
+
+    Ok(())
+}
+
+/// Runs both sides of the pair at `index` on generated inputs, returning the first one they
+/// disagree on. A size is picked at random for each input, up to the largest of `cases`.
+fn first_mismatch(
+    solution: &dyn DynSolution,
+    generator: &dyn DynGenerator,
+    index: usize,
+    cases: Cases,
+) -> Result<Option<Mismatch>, String> {
+    let day = solution.day();
+    let params = solution.params(&BTreeMap::new())?;
+    let sizes = generator.sizes();
+    let max_size = cases
+        .max_size
+        .unwrap_or_else(|| generator.default_size())
+        .min(*sizes.end());
+    if max_size < *sizes.start() {
+        return Err(format!(
+            "day {}: the size must be at least {}",
+            day,
+            sizes.start()
+        ));
+    }
+
+    if cases.count > 0 && cases.seed.checked_add(cases.count - 1).is_none() {
+        return Err(format!(
+            "--seed {} is too large for {} cases",
+            cases.seed, cases.count
+        ));
+    }
+
+    let mut rng = Rng::new(cases.seed);
+    for seed in (0..cases.count).map(|offset| cases.seed + offset) {
+        let size = rng.usize(*sizes.start()..max_size + 1);
+        let input = generator.generate(seed, size, &BTreeMap::new())?;
+        let run = |text: &str| outcomes(solution, params.as_ref(), index, text);
+
+        let [reference, optimised] = run(&input).ok_or_else(|| {
+            format!(
+                "day {}: the input generated from seed {} with size {} does not parse",
+                day, seed, size
+            )
+        })?;
+        let Some(failure) = failure(&reference, &optimised) else {
+            continue;
+        };
+
+        // A smaller input only counts if it fails the same way, not e.g. by panicking instead
+        let input = minimise(&input, |text| {
+            run(text).is_some_and(|[reference, optimised]| {
+                self::failure(&reference, &optimised) == Some(failure)
+            })
+        });
+        let [reference, optimised] = run(&input).unwrap();
+        return Ok(Some(Mismatch {
+            day,
+            pair: solution.pair_names()[index],
+            seed,
+            size,
+            input,
+            reference,
+            optimised,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// What both sides of the pair at `index` give on `text`, or `None` if it does not parse.
+fn outcomes(
+    solution: &dyn DynSolution,
+    params: &dyn Any,
+    index: usize,
+    text: &str,
+) -> Option<[Outcome; 2]> {
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| solution.parse(text)))
+        .ok()?
+        .ok()?;
+
+    let side = |side| match panic::catch_unwind(AssertUnwindSafe(|| {
+        solution.solve_pair(parsed.as_ref(), params, index, side)
+    })) {
+        Ok(answer) => Outcome::Answer(answer.to_string()),
+        Err(payload) => Outcome::Panic(panic_message(payload.as_ref())),
+    };
+    Some([side(Side::Reference), side(Side::Optimised)])
+}
+
+/// Which sides panicked, if the two did not give the same answer.
+fn failure(reference: &Outcome, optimised: &Outcome) -> Option<(bool, bool)> {
+    match (reference, optimised) {
+        (Outcome::Answer(a), Outcome::Answer(b)) if a == b => None,
+        _ => Some((
+            matches!(reference, Outcome::Panic(_)),
+            matches!(optimised, Outcome::Panic(_)),
+        )),
+    }
+}
+
+/// Shrinks an input while `fails` still holds for it: first whole paragraphs are left out,
+/// then lines, then the items of a line separated by commas or spaces.
+fn minimise(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let text = |units: &[String], separator: &str| units.join(separator) + "\n";
+
+    let paragraphs = input
+        .trim_end_matches('\n')
+        .split("\n\n")
+        .map(String::from)
+        .collect();
+    let paragraphs = remove_units(paragraphs, |units| fails(&text(units, "\n\n")));
+
+    let lines = paragraphs
+        .join("\n\n")
+        .split('\n')
+        .map(String::from)
+        .collect();
+    let mut lines = remove_units(lines, |units| fails(&text(units, "\n")));
+
+    for index in 0..lines.len() {
+        let Some(separator) = [", ", ",", " "]
+            .into_iter()
+            .find(|separator| lines[index].contains(separator))
+        else {
+            continue;
+        };
+
+        let items = lines[index].split(separator).map(String::from).collect();
+        let items = remove_units(items, |items| {
+            let mut candidate = lines.clone();
+            candidate[index] = items.join(separator);
+            fails(&text(&candidate, "\n"))
+        });
+        lines[index] = items.join(separator);
+    }
+
+    text(&lines, "\n")
+}
+
+/// Leaves out as many units as `fails` allows, trying runs of half of them first and
+/// single units last. At least one unit is always kept.
+fn remove_units(mut units: Vec<String>, mut fails: impl FnMut(&[String]) -> bool) -> Vec<String> {
+    let mut run = units.len() / 2;
+    while run > 0 {
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + run).min(units.len());
+            let candidate: Vec<String> = units[..start]
+                .iter()
+                .chain(&units[end..])
+                .cloned()
+                .collect();
+            if !candidate.is_empty() && fails(&candidate) {
+                units = candidate;
+            } else {
+                start = end;
+            }
+        }
+        run /= 2;
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::answer::Answer;
+    use crate::aoc::parse::{number, ParseError};
+    use crate::aoc::solution::{Pair, Solution};
+    use crate::gen::Generator;
+
+    /// Sums a list of numbers, but the optimised side forgets those above 50.
+    struct Forgetful;
+
+    impl Solution for Forgetful {
+        type Input = Vec<i64>;
+        type Params = ();
+
+        const DAY: u8 = 1;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.lines().map(|line| number(input, line)).collect()
+        }
+
+        fn part1(input: &Self::Input, _params: &Self::Params) -> Answer {
+            input.iter().sum::<i64>().into()
+        }
+
+        fn pairs() -> Vec<Pair<Self::Input, Self::Params>> {
+            vec![Pair {
+                name: "sum",
+                reference: Self::part1,
+                optimised: |input, _| input.iter().filter(|&&n| n <= 50).sum::<i64>().into(),
+            }]
+        }
+    }
+
+    /// Numbers below 100, one per line.
+    struct Numbers;
+
+    impl Generator for Numbers {
+        type Options = ();
+
+        const DAY: u8 = 1;
+        const SIZE: usize = 40;
+
+        fn generate(rng: &mut Rng, size: usize, _options: &Self::Options) -> String {
+            (0..size)
+                .map(|_| format!("{}\n", rng.i64(0..100)))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_minimise() {
+        let input = "1 2 3\n4 5 6\n\n7, 8, 9\n";
+        assert_eq!(minimise(input, |text| text.contains('5')), "5\n");
+        assert_eq!(
+            minimise(input, |text| text.contains('2') && text.contains('8')),
+            "2\n8\n"
+        );
+        assert_eq!(minimise(input, |text| text.contains("\n\n7")), "6\n\n7\n");
+        assert_eq!(minimise(input, |_| false), input);
+    }
+
+    #[test]
+    fn test_finds_and_minimises_a_mismatch() {
+        let cases = Cases {
+            count: 20,
+            seed: 0,
+            max_size: None,
+        };
+        let mismatch = first_mismatch(&Forgetful, &Numbers, 0, cases)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!((mismatch.day, mismatch.pair, mismatch.seed), (1, "sum", 0));
+        let number: i64 = mismatch.input.trim().parse().unwrap();
+        assert!(number > 50);
+        assert_eq!(mismatch.reference, Outcome::Answer(number.to_string()));
+        assert_eq!(mismatch.optimised, Outcome::Answer("0".to_string()));
+        assert!(mismatch.to_string().contains("mismatch on seed 0"));
+    }
+
+    #[test]
+    fn test_rejects_seeds_past_the_last() {
+        let cases = Cases {
+            count: 2,
+            seed: u64::MAX,
+            max_size: None,
+        };
+        assert!(first_mismatch(&Forgetful, &Numbers, 0, cases).is_err());
+
+        let last = Cases { count: 1, ..cases };
+        assert!(first_mismatch(&Forgetful, &Numbers, 0, last).is_ok());
+    }
+
+    #[test]
+    fn test_registered_pairs_agree() {
+        let mut checked = 0;
+        for solution in registry::SOLUTIONS {
+            let day = solution.day();
+            let cases = Cases {
+                count: 3,
+                seed: 0,
+                // Day 18 gets enough bytes to put obstacles in the way, but far too few to block
+                // the memory space; small inputs keep every search quick
+                max_size: Some(if day == 18 { 300 } else { 20 }),
+            };
+            for (index, name) in solution.pair_names().into_iter().enumerate() {
+                let generator = gen::get(day).unwrap();
+                if let Some(mismatch) = first_mismatch(*solution, generator, index, cases).unwrap()
+                {
+                    panic!("{}: {}", name, mismatch);
+                }
+                checked += 1;
+            }
+        }
+        assert_eq!(checked, 5);
+    }
+}
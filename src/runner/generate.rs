@@ -0,0 +1,119 @@
+use super::parse_day;
+use crate::aoc::params::parse_override;
+use crate::gen;
+use std::collections::BTreeMap;
+use std::fs;
+
+pub const USAGE: &str = "usage: advent-of-code-2024 gen <day> [--seed S] [--size N] [--param KEY=VALUE...] [--output PATH]";
+
+#[derive(Debug)]
+struct Options {
+    day: u8,
+    seed: u64,
+    /// The day's default size when not given.
+    size: Option<usize>,
+    /// Options of the generator set with `--param`.
+    overrides: BTreeMap<String, String>,
+    output: Option<String>,
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = parse_args(args)?;
+    let generator = gen::get(options.day)
+        .ok_or_else(|| format!("day {} has no input generator", options.day))?;
+
+    let size = options.size.unwrap_or_else(|| generator.default_size());
+    let input = generator.generate(options.seed, size, &options.overrides)?;
+    match options.output {
+        Some(path) => {
+            fs::write(&path, input).map_err(|err| format!("failed to write {}: {}", path, err))
+        }
+        None => {
+            print!("{}", input);
+            Ok(())
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut day = None;
+    let mut seed = 0;
+    let mut size = None;
+    let mut overrides = BTreeMap::new();
+    let mut output = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--seed" => {
+                seed = iter
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .ok_or_else(|| format!("--seed expects a number\n{}", USAGE))?;
+            }
+            "--size" => {
+                size = Some(
+                    iter.next()
+                        .and_then(|value| value.parse().ok())
+                        .ok_or_else(|| format!("--size expects a number\n{}", USAGE))?,
+                );
+            }
+            "--param" => {
+                let param = iter
+                    .next()
+                    .ok_or_else(|| format!("--param expects KEY=VALUE\n{}", USAGE))?;
+                let (key, value) = parse_override(param)?;
+                overrides.insert(key, value);
+            }
+            "--output" => {
+                output = Some(
+                    iter.next()
+                        .ok_or_else(|| format!("--output expects a path\n{}", USAGE))?
+                        .clone(),
+                );
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown option: {}\n{}", flag, USAGE))
+            }
+            value if day.is_none() => day = Some(parse_day(value)?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let day = day.ok_or_else(|| USAGE.to_string())?;
+    Ok(Options {
+        day,
+        seed,
+        size,
+        overrides,
+        output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&args(&[
+            "23", "--seed", "7", "--size", "40", "--param", "clique=5",
+        ]))
+        .unwrap();
+        assert_eq!((options.day, options.seed, options.size), (23, 7, Some(40)));
+        assert_eq!(options.overrides["clique"], "5");
+        assert_eq!(options.output, None);
+
+        let options = parse_args(&args(&["9"])).unwrap();
+        assert_eq!((options.seed, options.size), (0, None));
+
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["9", "--seed", "-1"])).is_err());
+        assert!(parse_args(&args(&["9", "--size"])).is_err());
+        assert!(parse_args(&args(&["9", "--param", "clique"])).is_err());
+    }
+}
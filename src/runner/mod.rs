@@ -0,0 +1,402 @@
+mod bench;
+mod differential;
+mod generate;
+mod report;
+mod scaffold;
+mod verify;
+mod watch;
+
+use crate::aoc::inputs;
+use crate::aoc::memory::{self, format_bytes, AllocStats};
+use crate::aoc::params::parse_override;
+use crate::aoc::registry;
+use crate::aoc::solution::DynSolution;
+use bench::format_duration;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "usage: advent-of-code-2024 <day> <part> [--input PATH | --name NAME | --example | --every-input] [--param KEY=VALUE...] [--alloc]\n       \
+                     advent-of-code-2024 --all [--name NAME | --example | --every-input] [--alloc]\n       \
+                     advent-of-code-2024 bench [DAY...] [--reps N] [--save] [--threshold PCT] [--alloc]\n       \
+                     advent-of-code-2024 verify [DAY...]\n       \
+                     advent-of-code-2024 report [DAY...] [--format json|csv] [--output PATH]\n       \
+                     advent-of-code-2024 watch <day> [--interval MS] [--once]\n       \
+                     advent-of-code-2024 gen <day> [--seed S] [--size N] [--param KEY=VALUE...] [--output PATH]\n       \
+                     advent-of-code-2024 diff [DAY...] [--cases N] [--size N] [--seed S]\n       \
+                     advent-of-code-2024 new-day <day> [--root DIR]";
+
+/// `profile` prints the time and allocations of every phase after the answers.
+enum Command {
+    Single {
+        day: u8,
+        part: u8,
+        input: InputSource,
+        /// Parameters set with `--param`, applied over those of the input.
+        overrides: BTreeMap<String, String>,
+        profile: bool,
+    },
+    All {
+        input: InputSource,
+        profile: bool,
+    },
+}
+
+enum InputSource {
+    /// A personal input or an example of the day, e.g. `input` or `example`.
+    Named(String),
+    /// Every personal input of the day.
+    Personal,
+    Path(String),
+}
+
+impl InputSource {
+    /// Names of the inputs selected for a day; a path names itself.
+    fn names(&self, day: u8) -> Vec<String> {
+        match self {
+            InputSource::Named(name) => vec![name.clone()],
+            InputSource::Personal => inputs::personal(day),
+            InputSource::Path(path) => vec![path.clone()],
+        }
+    }
+
+    fn read(&self, day: u8, name: &str) -> Result<String, String> {
+        match self {
+            InputSource::Path(path) => read_input(path),
+            _ => inputs::read(day, name),
+        }
+    }
+
+    /// Parameter overrides of an input; a file given by path has none.
+    fn params(&self, day: u8, name: &str) -> Result<BTreeMap<String, String>, String> {
+        match self {
+            InputSource::Path(_) => Ok(BTreeMap::new()),
+            _ => inputs::params(day, name),
+        }
+    }
+
+    /// Label printed after the part, naming the input when several may be run.
+    fn label(&self, name: &str) -> String {
+        match self {
+            InputSource::Personal => format!(" [{}]", name),
+            _ => String::new(),
+        }
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("bench") => return bench::run(&args[1..]),
+        Some("verify") => return verify::run(&args[1..]),
+        Some("report") => return report::run(&args[1..]),
+        Some("watch") => return watch::run(&args[1..]),
+        Some("gen") => return generate::run(&args[1..]),
+        Some("diff") => return differential::run(&args[1..]),
+        Some("new-day") => return scaffold::run(&args[1..]),
+        Some("help" | "--help") => {
+            println!(
+                "{}\n\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+                USAGE,
+                bench::USAGE,
+                verify::USAGE,
+                report::USAGE,
+                watch::USAGE,
+                generate::USAGE,
+                differential::USAGE,
+                scaffold::USAGE
+            );
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    match parse_args(args)? {
+        Command::Single {
+            day,
+            part,
+            input,
+            overrides,
+            profile,
+        } => {
+            for name in input.names(day) {
+                solve_input(solution(day)?, &input, &name, &[part], &overrides, profile)?;
+            }
+        }
+        Command::All { input, profile } => {
+            for solution in registry::SOLUTIONS {
+                let parts: Vec<u8> = (1..=solution.parts()).collect();
+                for name in input.names(solution.day()) {
+                    solve_input(*solution, &input, &name, &parts, &BTreeMap::new(), profile)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses an input once and prints the answers of `parts`, followed by the time and
+/// allocations of every phase when profiling.
+fn solve_input(
+    solution: &dyn DynSolution,
+    input: &InputSource,
+    name: &str,
+    parts: &[u8],
+    overrides: &BTreeMap<String, String>,
+    profile: bool,
+) -> Result<(), String> {
+    let day = solution.day();
+    let text = input.read(day, name)?;
+    let mut params = input.params(day, name)?;
+    params.extend(overrides.clone());
+    let params = solution.params(&params)?;
+
+    let (parsed, time, allocs) = profiled(|| parse(solution, &text));
+    let parsed = parsed?;
+    let mut phases = vec![("parse".to_string(), time, allocs)];
+
+    for &part in parts {
+        let (answer, time, allocs) =
+            profiled(|| solution.solve(parsed.as_ref(), params.as_ref(), part));
+        println!("Day {} part {}{}: {}", day, part, input.label(name), answer);
+        phases.push((format!("part{}", part), time, allocs));
+    }
+
+    if profile {
+        for (phase, time, allocs) in phases {
+            println!(
+                "  {:<6} {:>10} {:>10} allocs {:>10} allocated {:>10} peak",
+                phase,
+                format_duration(time),
+                allocs.allocations,
+                format_bytes(allocs.bytes),
+                format_bytes(allocs.peak)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs `f`, returning how long it took and what it allocated.
+fn profiled<T>(f: impl FnOnce() -> T) -> (T, Duration, AllocStats) {
+    let start = Instant::now();
+    let (result, allocs) = memory::measure(f);
+    (result, start.elapsed(), allocs)
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut input = InputSource::Named("input".to_string());
+    let mut all = false;
+    let mut profile = false;
+    let mut overrides = BTreeMap::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--alloc" => profile = true,
+            "--param" => {
+                let param = iter
+                    .next()
+                    .ok_or_else(|| format!("--param expects KEY=VALUE\n{}", USAGE))?;
+                let (key, value) = parse_override(param)?;
+                overrides.insert(key, value);
+            }
+            "--example" => input = InputSource::Named(inputs::EXAMPLE.to_string()),
+            "--every-input" => input = InputSource::Personal,
+            "--name" => {
+                let name = iter
+                    .next()
+                    .ok_or_else(|| format!("--name expects an input name\n{}", USAGE))?;
+                input = InputSource::Named(name.clone());
+            }
+            "--input" => {
+                let path = iter
+                    .next()
+                    .ok_or_else(|| format!("--input expects a path\n{}", USAGE))?;
+                input = InputSource::Path(path.clone());
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown option: {}\n{}", flag, USAGE))
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    if all {
+        if !positional.is_empty() {
+            return Err(format!("--all does not take a day or part\n{}", USAGE));
+        }
+        if let InputSource::Path(_) = input {
+            return Err(format!("--all cannot be combined with --input\n{}", USAGE));
+        }
+        if !overrides.is_empty() {
+            return Err(format!("--all cannot be combined with --param\n{}", USAGE));
+        }
+        return Ok(Command::All { input, profile });
+    }
+
+    match positional[..] {
+        [day, part] => {
+            let day = parse_day(day)?;
+            let part = parse_part(day, part)?;
+            Ok(Command::Single {
+                day,
+                part,
+                input,
+                overrides,
+                profile,
+            })
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn solution(day: u8) -> Result<&'static dyn DynSolution, String> {
+    registry::get(day).ok_or_else(|| format!("unknown day: {} (expected 1-25)", day))
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+    let day = value
+        .parse::<u8>()
+        .map_err(|_| format!("unknown day: {} (expected 1-25)", value))?;
+    solution(day).map(|solution| solution.day())
+}
+
+fn parse_part(day: u8, value: &str) -> Result<u8, String> {
+    match value.parse::<u8>() {
+        Ok(part) if (1..=solution(day)?.parts()).contains(&part) => Ok(part),
+        _ => Err(format!("unknown part for day {}: {}", day, value)),
+    }
+}
+
+/// Parses an input, rendering a parse error with a caret under the offending character.
+fn parse(solution: &dyn DynSolution, text: &str) -> Result<Box<dyn Any>, String> {
+    solution.parse(text).map_err(|err| err.render(text))
+}
+
+fn read_input(path: impl AsRef<Path>) -> Result<String, String> {
+    let path = path.as_ref();
+    fs::read_to_string(path)
+        .map_err(|err| format!("failed to read input file {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_single_day_with_example() {
+        match parse_args(&args(&["6", "2", "--example"])).unwrap() {
+            Command::Single {
+                day,
+                part,
+                input,
+                overrides,
+                profile,
+            } => {
+                assert_eq!((day, part), (6, 2));
+                assert!(overrides.is_empty());
+                assert!(!profile);
+                assert_eq!(input.names(day), vec!["example"]);
+                assert_eq!(input.read(day, "example"), inputs::read(6, "example"));
+            }
+            Command::All { .. } => panic!("expected a single day"),
+        }
+    }
+
+    #[test]
+    fn test_parse_custom_input_path() {
+        match parse_args(&args(&["1", "1", "--input", "other.txt"])).unwrap() {
+            Command::Single { input, .. } => assert_eq!(input.names(1), vec!["other.txt"]),
+            Command::All { .. } => panic!("expected a single day"),
+        }
+    }
+
+    #[test]
+    fn test_parse_named_and_every_input() {
+        match parse_args(&args(&["6", "1", "--name", "alice"])).unwrap() {
+            Command::Single { input, .. } => assert_eq!(input.names(6), vec!["alice"]),
+            Command::All { .. } => panic!("expected a single day"),
+        }
+
+        match parse_args(&args(&["--all", "--every-input", "--alloc"])).unwrap() {
+            Command::All { input, profile } => {
+                assert!(profile);
+                let names = input.names(1);
+                assert_eq!(names, inputs::personal(1));
+                assert!(!names.contains(&"example".to_string()));
+            }
+            Command::Single { .. } => panic!("expected every day"),
+        }
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let parsed = parse_args(&args(&[
+            "14", "1", "--param", "width=11", "--param", "height=7",
+        ]));
+        match parsed.unwrap() {
+            Command::Single { overrides, .. } => {
+                assert_eq!(overrides.get("width").map(String::as_str), Some("11"));
+                assert_eq!(overrides.get("height").map(String::as_str), Some("7"));
+            }
+            Command::All { .. } => panic!("expected a single day"),
+        }
+
+        assert!(parse_args(&args(&["14", "1", "--param", "width"])).is_err());
+        assert!(parse_args(&args(&["--all", "--param", "width=11"])).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_day_and_part() {
+        assert!(parse_args(&args(&["26", "1"])).is_err());
+        assert!(parse_args(&args(&["1", "3"])).is_err());
+        assert!(parse_args(&args(&["25", "2"])).is_err());
+        assert!(parse_args(&args(&["1"])).is_err());
+    }
+
+    #[test]
+    fn test_missing_input_file_is_an_error() {
+        let result = run(&args(&["1", "1", "--input", "input/day1/missing.txt"]));
+        assert!(result.unwrap_err().contains("input/day1/missing.txt"));
+    }
+
+    #[test]
+    fn test_solve_example() {
+        let solution = solution(1).unwrap();
+        let parsed = parse(solution, &inputs::read(1, "example").unwrap()).unwrap();
+        let params = solution.params(&BTreeMap::new()).unwrap();
+        assert_eq!(
+            solution
+                .solve(parsed.as_ref(), params.as_ref(), 1)
+                .to_string(),
+            "11"
+        );
+    }
+
+    #[test]
+    fn test_profiled_counts_allocations() {
+        let (parsed, _, allocs) = profiled(|| parse(solution(1).unwrap(), "3   4\n4   3\n"));
+        assert!(parsed.is_ok());
+        assert!(allocs.allocations > 0);
+        assert!(allocs.peak > 0 && allocs.peak <= allocs.bytes);
+    }
+
+    #[test]
+    fn test_parse_error_shows_the_offending_line() {
+        let err = parse(solution(1).unwrap(), "3   4\n4   x\n").unwrap_err();
+        assert_eq!(
+            err,
+            "day 1: line 2, column 5: expected a number\n  |\n2 | 4   x\n  |     ^"
+        );
+    }
+}
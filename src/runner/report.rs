@@ -0,0 +1,438 @@
+use super::parse_day;
+use super::verify::input_names;
+use crate::aoc::inputs;
+use crate::aoc::manifest::Manifest;
+use crate::aoc::registry;
+use crate::aoc::solution::DynSolution;
+use std::any::Any;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+pub const USAGE: &str =
+    "usage: advent-of-code-2024 report [DAY...] [--format json|csv] [--output PATH]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Csv,
+}
+
+/// Outcome of one part on one input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the manifest.
+    Ok,
+    /// The answer differs from the manifest.
+    Mismatch,
+    /// The part ran, but the manifest has no answer to compare with.
+    Unchecked,
+    /// Parsing or solving panicked.
+    Panic,
+    /// The input could not be read or parsed.
+    Error,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Mismatch => "mismatch",
+            Status::Unchecked => "unchecked",
+            Status::Panic => "panic",
+            Status::Error => "error",
+        }
+    }
+
+    fn is_failure(self) -> bool {
+        matches!(self, Status::Mismatch | Status::Panic | Status::Error)
+    }
+}
+
+/// One line of the report: a single part of a day run on a single input.
+#[derive(Debug)]
+pub struct Row {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    pub status: Status,
+    pub message: Option<String>,
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut days = Vec::new();
+    let mut format = Format::Json;
+    let mut output = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match iter.next().map(String::as_str) {
+                    Some("json") => Format::Json,
+                    Some("csv") => Format::Csv,
+                    _ => return Err(format!("--format expects json or csv\n{}", USAGE)),
+                }
+            }
+            "--output" => {
+                output = Some(
+                    iter.next()
+                        .ok_or_else(|| format!("--output expects a path\n{}", USAGE))?,
+                )
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown option: {}\n{}", flag, USAGE))
+            }
+            day => days.push(parse_day(day)?),
+        }
+    }
+    if days.is_empty() {
+        days = registry::SOLUTIONS.iter().map(|s| s.day()).collect();
+    }
+
+    let rows = quietly(|| {
+        let mut rows = Vec::new();
+        for &day in &days {
+            rows.extend(report_day(day)?);
+        }
+        Ok::<_, String>(rows)
+    })?;
+
+    let document = match format {
+        Format::Json => to_json(&rows),
+        Format::Csv => to_csv(&rows),
+    };
+    match output {
+        Some(path) => {
+            fs::write(path, document).map_err(|err| format!("failed to write {}: {}", path, err))?
+        }
+        None => print!("{}", document),
+    }
+
+    let failures = rows.iter().filter(|row| row.status.is_failure()).count();
+    if failures > 0 {
+        return Err(format!("{} part(s) failed", failures));
+    }
+
+    Ok(())
+}
+
+/// Runs `f` with the panic hook silenced, so caught panics do not clutter the report.
+pub(super) fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(hook);
+    result
+}
+
+/// Reports every input of a day. Example parts without a known answer are not run,
+/// since puzzle examples often only apply to one of the parts.
+fn report_day(day: u8) -> Result<Vec<Row>, String> {
+    let solution = registry::get(day).ok_or_else(|| format!("unknown day: {}", day))?;
+    let manifest = Manifest::load(day)?;
+
+    let personal = inputs::personal(day);
+
+    let mut rows = Vec::new();
+    for name in &input_names(day, &manifest) {
+        let parts: Vec<u8> = (1..=solution.parts())
+            .filter(|&part| personal.contains(name) || manifest.expected(name, part).is_some())
+            .collect();
+
+        let loaded = inputs::read(day, name).and_then(|text| {
+            let params = solution.params(&inputs::params(day, name)?)?;
+            Ok((text, params))
+        });
+        match loaded {
+            Ok((text, params)) => rows.extend(report_input(
+                solution,
+                &manifest,
+                name,
+                &text,
+                params.as_ref(),
+                &parts,
+            )),
+            Err(err) => rows.extend(parts.iter().map(|&part| Row {
+                message: Some(err.clone()),
+                ..empty_row(solution, name, part)
+            })),
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Parses an input once and solves the given parts, recording parse errors and panics
+/// as failed rows.
+fn report_input(
+    solution: &dyn DynSolution,
+    manifest: &Manifest,
+    name: &str,
+    text: &str,
+    params: &dyn Any,
+    parts: &[u8],
+) -> Vec<Row> {
+    let start = Instant::now();
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| solution.parse(text)));
+    let parse_time = start.elapsed();
+
+    let (status, message) = match parsed {
+        Ok(Ok(parsed)) => {
+            return solve_parts(solution, manifest, name, parsed, params, parse_time, parts)
+        }
+        Ok(Err(err)) => (Status::Error, err.to_string()),
+        Err(payload) => (
+            Status::Panic,
+            format!("parse panicked: {}", panic_message(payload.as_ref())),
+        ),
+    };
+
+    parts
+        .iter()
+        .map(|&part| Row {
+            parse_time,
+            status,
+            message: Some(message.clone()),
+            ..empty_row(solution, name, part)
+        })
+        .collect()
+}
+
+fn solve_parts(
+    solution: &dyn DynSolution,
+    manifest: &Manifest,
+    name: &str,
+    parsed: Box<dyn Any>,
+    params: &dyn Any,
+    parse_time: Duration,
+    parts: &[u8],
+) -> Vec<Row> {
+    parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = panic::catch_unwind(AssertUnwindSafe(|| {
+                solution.solve(parsed.as_ref(), params, part).to_string()
+            }));
+            let solve_time = start.elapsed();
+
+            let (answer, status, message) = match (answer, manifest.expected(name, part)) {
+                (Err(payload), _) => (None, Status::Panic, Some(panic_message(payload.as_ref()))),
+                (Ok(answer), Some(expected)) if answer == expected => {
+                    (Some(answer), Status::Ok, None)
+                }
+                (Ok(answer), Some(expected)) => (
+                    Some(answer),
+                    Status::Mismatch,
+                    Some(format!("expected {}", expected)),
+                ),
+                (Ok(answer), None) => (Some(answer), Status::Unchecked, None),
+            };
+
+            Row {
+                answer,
+                parse_time,
+                solve_time,
+                status,
+                message,
+                ..empty_row(solution, name, part)
+            }
+        })
+        .collect()
+}
+
+fn empty_row(solution: &dyn DynSolution, name: &str, part: u8) -> Row {
+    Row {
+        day: solution.day(),
+        part,
+        input: name.to_string(),
+        answer: None,
+        parse_time: Duration::ZERO,
+        solve_time: Duration::ZERO,
+        status: Status::Error,
+        message: None,
+    }
+}
+
+pub(super) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn to_json(rows: &[Row]) -> String {
+    let optional = |value: &Option<String>| match value {
+        Some(value) => json_string(value),
+        None => "null".to_string(),
+    };
+
+    let results: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            format!(
+                "    {{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \
+                 \"parse_ns\": {}, \"solve_ns\": {}, \"status\": \"{}\", \"message\": {}}}",
+                row.day,
+                row.part,
+                json_string(&row.input),
+                optional(&row.answer),
+                row.parse_time.as_nanos(),
+                row.solve_time.as_nanos(),
+                row.status.as_str(),
+                optional(&row.message)
+            )
+        })
+        .collect();
+
+    format!("{{\n  \"results\": [\n{}\n  ]\n}}\n", results.join(",\n"))
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn to_csv(rows: &[Row]) -> String {
+    let mut csv = String::from("day,part,input,answer,parse_ns,solve_ns,status,message\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            row.day,
+            row.part,
+            csv_field(&row.input),
+            csv_field(row.answer.as_deref().unwrap_or("")),
+            row.parse_time.as_nanos(),
+            row.solve_time.as_nanos(),
+            row.status.as_str(),
+            csv_field(row.message.as_deref().unwrap_or(""))
+        ));
+    }
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::answer::Answer;
+    use crate::aoc::parse::{number, ParseError};
+    use crate::aoc::solution::Solution;
+
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Input = Vec<i64>;
+        type Params = ();
+
+        const DAY: u8 = 1;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.split(',').map(|n| number(input, n)).collect()
+        }
+
+        fn part1(input: &Self::Input, _params: &()) -> Answer {
+            input.iter().sum::<i64>().into()
+        }
+
+        fn part2(input: &Self::Input, _params: &()) -> Answer {
+            input[10].into()
+        }
+    }
+
+    fn manifest() -> Manifest {
+        Manifest::parse("input.part1 = 7\ninput.part2 = 3\n").unwrap()
+    }
+
+    #[test]
+    fn test_panics_are_caught_per_part() {
+        let rows = report_input(&Fragile, &manifest(), "input", "3,4", &(), &[1, 2]);
+
+        assert_eq!(rows[0].status, Status::Ok);
+        assert_eq!(rows[0].answer.as_deref(), Some("7"));
+        assert_eq!(rows[1].status, Status::Panic);
+        assert!(rows[1].message.as_ref().unwrap().contains("out of bounds"));
+    }
+
+    #[test]
+    fn test_parse_error_fails_every_part() {
+        let rows = report_input(&Fragile, &manifest(), "input", "3,x", &(), &[1, 2]);
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.status == Status::Error));
+        assert_eq!(
+            rows[0].message.as_deref(),
+            Some("day 1: line 1, column 3: expected a number")
+        );
+    }
+
+    #[test]
+    fn test_mismatch_and_unchecked() {
+        let rows = report_input(&Fragile, &manifest(), "input", "1,2", &(), &[1]);
+        assert_eq!(rows[0].status, Status::Mismatch);
+        assert_eq!(rows[0].message.as_deref(), Some("expected 7"));
+
+        let rows = report_input(&Fragile, &manifest(), "alice", "1,2", &(), &[1]);
+        assert_eq!(rows[0].status, Status::Unchecked);
+    }
+
+    #[test]
+    fn test_report_day() {
+        let rows = report_day(1).unwrap();
+        assert!(rows
+            .iter()
+            .any(|row| row.input == "example" && row.part == 2 && row.status == Status::Ok));
+        assert!(rows.iter().all(|row| !row.status.is_failure()));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let rows = report_input(&Fragile, &manifest(), "input", "3,4", &(), &[1]);
+        let json = to_json(&rows);
+        assert!(json.starts_with("{\n  \"results\": [\n    {\"day\": 1, \"part\": 1, "));
+        assert!(json.contains("\"input\": \"input\", \"answer\": \"7\""));
+        assert!(json.contains("\"status\": \"ok\", \"message\": null}"));
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let rows = report_input(&Fragile, &manifest(), "input", "1,2", &(), &[1]);
+        let csv = to_csv(&rows);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "day,part,input,answer,parse_ns,solve_ns,status,message"
+        );
+        assert!(lines[1].starts_with("1,1,input,3,"));
+        assert!(lines[1].ends_with(",mismatch,expected 7"));
+        assert_eq!(csv_field("6,1"), "\"6,1\"");
+    }
+}
@@ -0,0 +1,240 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "usage: advent-of-code-2024 new-day <day> [--root DIR]";
+
+const TEMPLATE: &str = include_str!("templates/solution.rs.tmpl");
+const ANSWERS_HEADER: &str = "# <input file>.part<N> = <expected answer>\n";
+const EXAMPLES_HEADER: &str = "# == <name> [part<N>=<answer>...] [<param>=<value>...], \
+                               followed by the example input\n== example\n";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut day = None;
+    let mut root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--root" => {
+                root = iter
+                    .next()
+                    .map(PathBuf::from)
+                    .ok_or_else(|| format!("--root expects a directory\n{}", USAGE))?;
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown option: {}\n{}", flag, USAGE))
+            }
+            value if day.is_none() => day = Some(parse_new_day(value)?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let day = day.ok_or_else(|| USAGE.to_string())?;
+
+    for path in scaffold(day, &root)? {
+        println!("wrote {}", path.display());
+    }
+    println!(
+        "Paste the example and its answers into input/day{day}/examples.txt, add your input \
+         as input/day{day}/input.txt and run `cargo run -- watch {day}`"
+    );
+
+    Ok(())
+}
+
+/// Days are not looked up in the registry here, since the new day is not registered yet.
+fn parse_new_day(value: &str) -> Result<u8, String> {
+    match value.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("unknown day: {} (expected 1-25)", value)),
+    }
+}
+
+/// Creates the module, solution, and input files of a day under `root`, declares the module
+/// in `main.rs` and registers the solution. Returns every file written.
+fn scaffold(day: u8, root: &Path) -> Result<Vec<PathBuf>, String> {
+    let module_dir = root.join("src").join(format!("day{}", day));
+    if module_dir.exists() {
+        return Err(format!("{} already exists", module_dir.display()));
+    }
+
+    let main_path = root.join("src").join("main.rs");
+    let registry_path = root.join("src").join("aoc").join("registry.rs");
+    let main = add_module(&read(&main_path)?, day)?;
+    let registry = add_registry_entry(&read(&registry_path)?, day)?;
+
+    let input_dir = root.join("input").join(format!("day{}", day));
+    let mut files = vec![
+        (
+            module_dir.join("mod.rs"),
+            "pub(crate) mod solution;\n".to_string(),
+        ),
+        (module_dir.join("solution.rs"), render(day)),
+        (main_path, main),
+        (registry_path, registry),
+    ];
+    for (name, contents) in [
+        ("examples.txt", EXAMPLES_HEADER),
+        ("answers.txt", ANSWERS_HEADER),
+    ] {
+        let path = input_dir.join(name);
+        if !path.exists() {
+            files.push((path, contents.to_string()));
+        }
+    }
+
+    for (path, contents) in &files {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("failed to create {}: {}", parent.display(), err))?;
+        }
+        fs::write(path, contents)
+            .map_err(|err| format!("failed to write {}: {}", path.display(), err))?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("failed to read {}: {}", path.display(), err))
+}
+
+fn render(day: u8) -> String {
+    TEMPLATE.replace("{{day}}", &day.to_string())
+}
+
+/// Adds `mod dayN;` to the module declarations of `main.rs`, keeping rustfmt's ordering.
+fn add_module(main: &str, day: u8) -> Result<String, String> {
+    let declaration = format!("mod day{};", day);
+    let mut lines: Vec<&str> = main.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return Err(format!("main.rs already declares day{}", day));
+    }
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("mod "))
+        .ok_or("main.rs has no module declarations")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .take_while(|line| line.starts_with("mod "))
+            .count();
+
+    lines.insert(end, &declaration);
+    lines[start..=end].sort_unstable_by_key(|line| line.trim_end_matches(';'));
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Inserts `&dayN::solution::DayN` into `SOLUTIONS`, keeping the entries ordered by day.
+fn add_registry_entry(registry: &str, day: u8) -> Result<String, String> {
+    let entry = format!("    &day{day}::solution::Day{day},");
+    let entry_day = |line: &str| -> Option<u8> {
+        line.trim()
+            .strip_prefix("&day")?
+            .split_once("::")?
+            .0
+            .parse()
+            .ok()
+    };
+
+    let mut lines: Vec<&str> = registry.lines().collect();
+    if lines.iter().any(|line| entry_day(line) == Some(day)) {
+        return Err(format!("day{} is already registered", day));
+    }
+
+    let start = lines
+        .iter()
+        .position(|line| line.contains("SOLUTIONS"))
+        .ok_or("registry.rs has no SOLUTIONS list")?;
+    let close = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "];")
+            .ok_or("registry.rs has an unterminated SOLUTIONS list")?;
+    let index = (start + 1..close)
+        .find(|&index| entry_day(lines[index]).is_some_and(|other| other > day))
+        .unwrap_or(close);
+
+    lines.insert(index, &entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const MAIN: &str = "mod aoc;\nmod day1;\nmod day2;\nmod runner;\n\nfn main() {}\n";
+    const REGISTRY: &str = "pub static SOLUTIONS: &[&dyn DynSolution] = &[\n    \
+                            &day1::solution::Day1,\n    &day2::solution::Day2,\n];\n";
+
+    #[test]
+    fn test_add_module() {
+        let main = add_module(MAIN, 12).unwrap();
+        assert!(main.starts_with("mod aoc;\nmod day1;\nmod day12;\nmod day2;\nmod runner;\n\n"));
+        assert!(add_module(MAIN, 2).is_err());
+    }
+
+    #[test]
+    fn test_add_registry_entry() {
+        let registry = add_registry_entry(REGISTRY, 5).unwrap();
+        assert!(registry.ends_with("Day2,\n    &day5::solution::Day5,\n];\n"));
+
+        let registry = add_registry_entry(&registry, 3).unwrap();
+        assert!(registry.contains("Day2,\n    &day3::solution::Day3,\n    &day5::"));
+
+        assert!(add_registry_entry(REGISTRY, 1).is_err());
+    }
+
+    #[test]
+    fn test_render_matches_repository_layout() {
+        let solution = render(7);
+        assert!(solution.contains("pub struct Day7;"));
+        assert!(solution.contains("const DAY: u8 = 7;"));
+        assert!(solution.contains("solution_tests!(Day7);"));
+        // The impl must keep up with the `Solution` trait, which this test does not compile
+        assert!(solution.contains("type Params = ();"));
+        assert!(
+            solution.contains("fn part1(input: &Self::Input, _params: &Self::Params) -> Answer")
+        );
+        assert!(
+            solution.contains("fn part2(input: &Self::Input, _params: &Self::Params) -> Answer")
+        );
+        assert!(!solution.contains("{{"));
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/aoc")).unwrap();
+        fs::write(root.join("src/main.rs"), MAIN).unwrap();
+        fs::write(root.join("src/aoc/registry.rs"), REGISTRY).unwrap();
+
+        let written = scaffold(3, &root).unwrap();
+        assert_eq!(written.len(), 6);
+        assert_eq!(
+            fs::read_to_string(root.join("src/day3/mod.rs")).unwrap(),
+            "pub(crate) mod solution;\n"
+        );
+        let examples = fs::read_to_string(root.join("input/day3/examples.txt")).unwrap();
+        assert_eq!(
+            crate::aoc::examples::parse(&examples).unwrap()[0].name,
+            "example"
+        );
+        assert!(fs::read_to_string(root.join("src/main.rs"))
+            .unwrap()
+            .contains("mod day3;"));
+        assert!(scaffold(3, &root).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_existing_day() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert!(scaffold(6, root).unwrap_err().contains("already exists"));
+        assert!(parse_new_day("26").is_err());
+    }
+}
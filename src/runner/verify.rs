@@ -0,0 +1,123 @@
+use super::{parse, parse_day};
+use crate::aoc::inputs;
+use crate::aoc::manifest::Manifest;
+use crate::aoc::registry;
+
+pub const USAGE: &str = "usage: advent-of-code-2024 verify [DAY...]";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Summary {
+    ok: usize,
+    mismatches: usize,
+    skipped: usize,
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut days = Vec::new();
+    for arg in args {
+        if arg.starts_with("--") {
+            return Err(format!("unknown option: {}\n{}", arg, USAGE));
+        }
+        days.push(parse_day(arg)?);
+    }
+    if days.is_empty() {
+        days = registry::SOLUTIONS.iter().map(|s| s.day()).collect();
+    }
+
+    let mut summary = Summary::default();
+    for day in days {
+        verify_day(day, &mut summary)?;
+    }
+
+    println!(
+        "{} ok, {} mismatched, {} without a known answer",
+        summary.ok, summary.mismatches, summary.skipped
+    );
+
+    if summary.mismatches > 0 {
+        return Err(format!(
+            "{} answer(s) differ from the manifests",
+            summary.mismatches
+        ));
+    }
+
+    Ok(())
+}
+
+/// Runs every part with a known answer on every example and personal input of the day,
+/// including inputs that only appear in the manifest. Parts without a known answer are
+/// reported but not run.
+fn verify_day(day: u8, summary: &mut Summary) -> Result<(), String> {
+    let solution = registry::get(day).ok_or_else(|| format!("unknown day: {}", day))?;
+    let manifest = Manifest::load(day)?;
+
+    for input in &input_names(day, &manifest) {
+        let input = input.as_str();
+        let text = inputs::read(day, input)?;
+        let parsed = parse(solution, &text)?;
+        let params = solution.params(&inputs::params(day, input)?)?;
+
+        for part in 1..=solution.parts() {
+            let label = format!("Day {:>2} part {} [{}]", day, part, input);
+            match manifest.expected(input, part) {
+                Some(expected) => {
+                    let answer = solution
+                        .solve(parsed.as_ref(), params.as_ref(), part)
+                        .to_string();
+                    if answer == expected {
+                        summary.ok += 1;
+                        println!("{:<28} ok        {}", label, answer);
+                    } else {
+                        summary.mismatches += 1;
+                        println!(
+                            "{:<28} MISMATCH  expected {}, got {}",
+                            label, expected, answer
+                        );
+                    }
+                }
+                None => {
+                    summary.skipped += 1;
+                    println!("{:<28} skipped   no known answer", label);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The inputs of a day followed by any input that only has answers in the manifest,
+/// so a missing input file is reported instead of silently skipped.
+pub fn input_names(day: u8, manifest: &Manifest) -> Vec<String> {
+    let mut names = inputs::names(day);
+    for input in manifest.inputs() {
+        if !names.iter().any(|name| name == input) {
+            names.push(input.to_string());
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_day() {
+        let mut summary = Summary::default();
+        verify_day(1, &mut summary).unwrap();
+        assert_eq!(
+            summary,
+            Summary {
+                ok: 4,
+                mismatches: 0,
+                skipped: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_day() {
+        assert!(run(&["26".to_string()]).is_err());
+    }
+}
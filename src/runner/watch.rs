@@ -0,0 +1,241 @@
+use super::bench::format_duration;
+use super::{parse, parse_day};
+use crate::aoc::inputs;
+use crate::aoc::manifest::Manifest;
+use crate::aoc::registry;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+pub const USAGE: &str = "usage: advent-of-code-2024 watch <day> [--interval MS] [--once]";
+
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+struct Options {
+    day: u8,
+    interval: Duration,
+    once: bool,
+}
+
+/// Modification times of every file under the watched directories.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = parse_args(args)?;
+
+    if options.once {
+        for line in check_day(options.day)? {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    let dirs = watched_dirs(options.day);
+    println!(
+        "Watching {} (Ctrl-C to stop)",
+        dirs.iter()
+            .map(|dir| dir.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" and ")
+    );
+
+    let mut last = snapshot(&dirs);
+    rerun(options.day);
+    loop {
+        thread::sleep(options.interval);
+        let current = snapshot(&dirs);
+        if current != last {
+            last = current;
+            rerun(options.day);
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut day = None;
+    let mut interval = DEFAULT_INTERVAL;
+    let mut once = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--once" => once = true,
+            "--interval" => {
+                interval = iter
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|&millis| millis > 0)
+                    .map(Duration::from_millis)
+                    .ok_or_else(|| format!("--interval expects milliseconds\n{}", USAGE))?;
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown option: {}\n{}", flag, USAGE))
+            }
+            value if day.is_none() => day = Some(parse_day(value)?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let day = day.ok_or_else(|| USAGE.to_string())?;
+    Ok(Options {
+        day,
+        interval,
+        once,
+    })
+}
+
+fn watched_dirs(day: u8) -> Vec<PathBuf> {
+    vec![
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join(format!("day{}", day)),
+        inputs::day_dir(day),
+    ]
+}
+
+fn snapshot(dirs: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for dir in dirs {
+        collect_mtimes(dir, &mut snapshot);
+    }
+    snapshot
+}
+
+fn collect_mtimes(dir: &Path, snapshot: &mut Snapshot) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_mtimes(&path, snapshot);
+        } else if let Ok(modified) = metadata.modified() {
+            snapshot.insert(path, modified);
+        }
+    }
+}
+
+/// Rebuilds the crate and checks the day in a child process, so source edits are picked up
+/// and a panicking solution does not stop the watch.
+fn rerun(day: u8) {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let status = Command::new(cargo)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["run", "--release", "--quiet", "--", "watch"])
+        .arg(day.to_string())
+        .arg("--once")
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!("day{} FAILED ({})", day, status),
+        Err(err) => println!("day{} FAILED (could not start cargo: {})", day, err),
+    }
+}
+
+/// Runs every part of a day on each of its inputs and returns one line per input with
+/// the answers, whether they match the manifest and how long they took.
+fn check_day(day: u8) -> Result<Vec<String>, String> {
+    let solution = registry::get(day).ok_or_else(|| format!("unknown day: {}", day))?;
+    let manifest = Manifest::load(day)?;
+
+    let mut lines = Vec::new();
+    for name in inputs::names(day) {
+        let text = inputs::read(day, &name)?;
+        let params = solution.params(&inputs::params(day, &name)?)?;
+
+        let start = Instant::now();
+        let parsed = parse(solution, &text)?;
+        let mut line = format!(
+            "day{} {:<8} parse ({})",
+            day,
+            name,
+            format_duration(start.elapsed())
+        );
+
+        for part in 1..=solution.parts() {
+            let start = Instant::now();
+            let answer = solution
+                .solve(parsed.as_ref(), params.as_ref(), part)
+                .to_string();
+            let elapsed = format_duration(start.elapsed());
+
+            let status = match manifest.expected(&name, part) {
+                Some(expected) if expected == answer => format!("ok {}", answer),
+                Some(expected) => format!("FAIL {} != {}", answer, expected),
+                None => format!("? {}", answer),
+            };
+            line.push_str(&format!("  part{} {} ({})", part, status, elapsed));
+        }
+
+        lines.push(line);
+    }
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&args(&["6", "--interval", "200"])).unwrap();
+        assert_eq!(
+            (options.day, options.interval),
+            (6, Duration::from_millis(200))
+        );
+        assert!(!options.once);
+
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["6", "7"])).is_err());
+        assert!(parse_args(&args(&["6", "--interval", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_detects_changes() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("input.txt");
+        fs::write(&file, "1").unwrap();
+        let dirs = vec![dir.clone()];
+
+        let before = snapshot(&dirs);
+        assert_eq!(before.len(), 1);
+
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_ne!(snapshot(&dirs), before);
+
+        fs::write(dir.join("alice.txt"), "2").unwrap();
+        assert_eq!(snapshot(&dirs).len(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_day() {
+        let lines = check_day(1).unwrap();
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("day1 example") && line.contains("part1 ok 11")));
+        assert!(lines.iter().all(|line| !line.contains("FAIL")));
+    }
+}
@@ -0,0 +1,121 @@
+/// A fixed-size set of the integers `0..len`, one bit each. As a visited set for a grid, key
+/// it by [`Grid::index_of`](crate::utils::grid::Grid::index_of) to avoid hashing entirely.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> BitSet {
+        BitSet {
+            words: vec![0; (len + 63) / 64],
+            len,
+        }
+    }
+
+    /// Size of the universe `0..len`, not the number of members.
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        let (word, bit) = self.locate(index);
+        self.words[word] & bit != 0
+    }
+
+    /// Adds `index` and returns whether it was not a member yet, like `HashSet::insert`.
+    pub fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = self.locate(index);
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    /// Removes `index` and returns whether it was a member.
+    #[allow(dead_code)]
+    pub fn remove(&mut self, index: usize) -> bool {
+        let (word, bit) = self.locate(index);
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    /// Empties the set, keeping its allocation for reuse.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Number of members.
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The members in increasing order.
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                (rest != 0).then(|| {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    index * 64 + bit
+                })
+            })
+        })
+    }
+
+    fn locate(&self, index: usize) -> (usize, u64) {
+        assert!(
+            index < self.len,
+            "index {} is outside a set of {}",
+            index,
+            self.len
+        );
+        (index / 64, 1 << (index % 64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = BitSet::new(130);
+        assert!(set.is_empty());
+
+        assert!(set.insert(0));
+        assert!(set.insert(64));
+        assert!(set.insert(129));
+        assert!(!set.insert(64));
+        assert!(set.contains(129));
+        assert!(!set.contains(128));
+        assert_eq!(set.count(), 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 64, 129]);
+
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 129]);
+
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.len(), 130);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a set of 10")]
+    fn test_out_of_range() {
+        BitSet::new(10).insert(10);
+    }
+}
@@ -0,0 +1,257 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+/// A multiset counting how often each key occurs. Counts are `u64` unless another width is
+/// asked for, and a key that was never counted has a count of zero.
+#[derive(Debug, Clone)]
+pub struct Counter<K, C = u64> {
+    counts: HashMap<K, C>,
+}
+
+/// The integer types a [`Counter`] can count with.
+pub trait Count: Copy + Ord + Default + From<u8> + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<C: Copy + Ord + Default + From<u8> + Add<Output = C> + Sub<Output = C>> Count for C {}
+
+impl<K, C> Counter<K, C>
+where
+    K: Eq + Hash,
+    C: Count,
+{
+    pub fn new() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Counts one more occurrence of `key`.
+    pub fn insert(&mut self, key: K) {
+        self.insert_n(key, C::from(1));
+    }
+
+    /// Counts `n` more occurrences of `key`.
+    pub fn insert_n(&mut self, key: K, n: C) {
+        if n > C::default() {
+            let count = self.counts.entry(key).or_default();
+            *count = *count + n;
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> C
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.counts.get(key).copied().unwrap_or_default()
+    }
+
+    /// Number of distinct keys.
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Sum of all counts.
+    #[allow(dead_code)]
+    pub fn total(&self) -> C {
+        self.counts
+            .values()
+            .fold(C::default(), |total, &count| total + count)
+    }
+
+    /// Adds every count of `other` to this counter.
+    #[allow(dead_code)]
+    pub fn merge(&mut self, other: &Counter<K, C>)
+    where
+        K: Clone,
+    {
+        for (key, &count) in &other.counts {
+            self.insert_n(key.clone(), count);
+        }
+    }
+
+    /// The `n` most frequent keys with their counts, ties broken by the smaller key.
+    #[allow(dead_code)]
+    pub fn most_common(&self, n: usize) -> Vec<(&K, C)>
+    where
+        K: Ord,
+    {
+        let mut entries: Vec<(&K, C)> = self.counts.iter().map(|(k, &c)| (k, c)).collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        entries.truncate(n);
+        entries
+    }
+
+    /// Every key with its count, in increasing order of keys.
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (&K, C)>
+    where
+        K: Ord,
+    {
+        let mut entries: Vec<(&K, C)> = self.counts.iter().map(|(k, &c)| (k, c)).collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries.into_iter()
+    }
+}
+
+impl<K, C> Default for Counter<K, C>
+where
+    K: Eq + Hash,
+    C: Count,
+{
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+impl<K: Eq + Hash, C: Count> PartialEq for Counter<K, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<K: Eq + Hash, C: Count> Eq for Counter<K, C> {}
+
+impl<K, C> FromIterator<K> for Counter<K, C>
+where
+    K: Eq + Hash,
+    C: Count,
+{
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K, C> Extend<K> for Counter<K, C>
+where
+    K: Eq + Hash,
+    C: Count,
+{
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+impl<K, C> Add for Counter<K, C>
+where
+    K: Eq + Hash,
+    C: Count,
+{
+    type Output = Counter<K, C>;
+
+    fn add(mut self, other: Counter<K, C>) -> Counter<K, C> {
+        for (key, count) in other.counts {
+            self.insert_n(key, count);
+        }
+        self
+    }
+}
+
+/// Subtracts counts like a multiset difference: keys whose count would drop to zero or
+/// below are removed, so unsigned counts never underflow.
+impl<K, C> Sub for Counter<K, C>
+where
+    K: Eq + Hash,
+    C: Count,
+{
+    type Output = Counter<K, C>;
+
+    fn sub(mut self, other: Counter<K, C>) -> Counter<K, C> {
+        for (key, removed) in other.counts {
+            if let Some(&count) = self.counts.get(&key) {
+                if count > removed {
+                    self.counts.insert(key, count - removed);
+                } else {
+                    self.counts.remove(&key);
+                }
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let counter: Counter<i32> = [2, 3, 1, 2, 1, 3, 2].into_iter().collect();
+
+        assert_eq!(counter.get(&2), 3);
+        assert_eq!(counter.get(&1), 2);
+        assert_eq!(counter.get(&7), 0);
+        assert_eq!(counter.len(), 3);
+        assert_eq!(counter.total(), 7);
+        assert_eq!(
+            counter.iter().collect::<Vec<_>>(),
+            vec![(&1, 2), (&2, 3), (&3, 2)]
+        );
+    }
+
+    #[test]
+    fn test_empty_and_negative_keys() {
+        let empty: Counter<i32> = std::iter::empty().collect();
+        assert!(empty.is_empty());
+        assert_eq!(empty.total(), 0);
+
+        let counter: Counter<i32, i32> = [-1, -2, -2, -3, -3, -3].into_iter().collect();
+        assert_eq!(counter.get(&-3), 3);
+        assert_eq!(counter.most_common(1), vec![(&-3, 3)]);
+    }
+
+    #[test]
+    fn test_most_common_breaks_ties_by_key() {
+        let counter: Counter<&str, u8> = "b a c a b d".split(' ').collect();
+
+        assert_eq!(
+            counter.most_common(3),
+            vec![(&"a", 2), (&"b", 2), (&"c", 1)]
+        );
+        assert_eq!(counter.most_common(10).len(), 4);
+    }
+
+    #[test]
+    fn test_borrowed_lookup() {
+        let counter: Counter<String> = ["XMAS", "SAMX", "XMAS"]
+            .map(String::from)
+            .into_iter()
+            .collect();
+
+        assert_eq!(counter.get("XMAS"), 2);
+        assert_eq!(counter.get("MAS"), 0);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a: Counter<char> = "aabbc".chars().collect();
+        let b: Counter<char> = "abbbd".chars().collect();
+
+        let sum = a.clone() + b.clone();
+        assert_eq!(
+            sum.iter().collect::<Vec<_>>(),
+            vec![(&'a', 3), (&'b', 5), (&'c', 1), (&'d', 1)]
+        );
+
+        let difference = a.clone() - b.clone();
+        assert_eq!(
+            difference.iter().collect::<Vec<_>>(),
+            vec![(&'a', 1), (&'c', 1)]
+        );
+
+        let mut merged = a;
+        merged.merge(&b);
+        assert_eq!(merged, sum);
+    }
+}
@@ -1,19 +1,22 @@
-pub struct DSU {
-    n: usize,
+/// Disjoint-set union over the elements `0..n`, with union by size and path compression.
+pub struct Dsu {
+    /// The parent of an element, or minus the size of its set for a leader.
     parent_or_size: Vec<isize>,
+    count: usize,
 }
 
-impl DSU {
+impl Dsu {
     pub fn new(n: usize) -> Self {
-        DSU {
-            n,
+        Dsu {
             parent_or_size: vec![-1; n],
+            count: n,
         }
     }
 
+    /// Joins the sets of `a` and `b` and returns the leader of the joined set.
     pub fn merge(&mut self, a: usize, b: usize) -> usize {
-        let mut x = self.leader(a);
-        let mut y = self.leader(b);
+        let mut x = self.find(a);
+        let mut y = self.find(b);
 
         if x == y {
             return x;
@@ -25,33 +28,192 @@ impl DSU {
 
         self.parent_or_size[x] += self.parent_or_size[y];
         self.parent_or_size[y] = x as isize;
+        self.count -= 1;
         x
     }
 
-    fn leader(&mut self, a: usize) -> usize {
-        if self.parent_or_size[a] < 0 {
-            a
-        } else {
-            let leader = self.leader(self.parent_or_size[a] as usize);
-            self.parent_or_size[a] = leader as isize;
-            leader
+    /// The leader of the set containing `a`. Every element on the way is pointed straight at
+    /// the leader, without recursing, so long chains cannot overflow the stack.
+    pub fn find(&mut self, a: usize) -> usize {
+        let mut leader = a;
+        while self.parent_or_size[leader] >= 0 {
+            leader = self.parent_or_size[leader] as usize;
         }
+
+        let mut current = a;
+        while current != leader {
+            let next = self.parent_or_size[current] as usize;
+            self.parent_or_size[current] = leader as isize;
+            current = next;
+        }
+        leader
+    }
+
+    #[allow(dead_code)]
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the set containing `a`.
+    pub fn size(&mut self, a: usize) -> usize {
+        let leader = self.find(a);
+        -self.parent_or_size[leader] as usize
+    }
+
+    /// Number of disjoint sets.
+    #[allow(dead_code)]
+    pub fn count(&self) -> usize {
+        self.count
     }
 
+    #[allow(dead_code)]
     pub fn groups(&mut self) -> Vec<Vec<usize>> {
-        let mut leader_buf = vec![0; self.n];
-        let mut group_size = vec![0; self.n];
+        let n = self.parent_or_size.len();
+        let mut result = vec![vec![]; n];
+        for i in 0..n {
+            let leader = self.find(i);
+            result[leader].push(i);
+        }
+
+        result.into_iter().filter(|x| !x.is_empty()).collect()
+    }
+}
+
+/// Disjoint-set union that can undo merges, for offline algorithms that explore and then
+/// backtrack, e.g. connectivity over time with a divide and conquer over queries. It uses
+/// union by size without path compression, so every `find` takes logarithmic time.
+#[allow(dead_code)]
+pub struct RollbackDsu {
+    parent_or_size: Vec<isize>,
+    count: usize,
+    /// The merges made, each as the absorbed leader and the size it had.
+    history: Vec<(usize, isize)>,
+}
 
-        for i in 0..self.n {
-            leader_buf[i] = self.leader(i);
-            group_size[leader_buf[i]] += 1;
+#[allow(dead_code)]
+impl RollbackDsu {
+    pub fn new(n: usize) -> Self {
+        RollbackDsu {
+            parent_or_size: vec![-1; n],
+            count: n,
+            history: Vec::new(),
         }
+    }
 
-        let mut result = vec![vec![]; self.n];
-        for i in 0..self.n {
-            result[leader_buf[i]].push(i);
+    /// Joins the sets of `a` and `b` and returns the leader of the joined set. Merging two
+    /// elements of one set changes nothing and is not recorded.
+    pub fn merge(&mut self, a: usize, b: usize) -> usize {
+        let mut x = self.find(a);
+        let mut y = self.find(b);
+
+        if x == y {
+            return x;
         }
 
-        result.into_iter().filter(|x| !x.is_empty()).collect()
+        if -self.parent_or_size[x] < -self.parent_or_size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+
+        self.history.push((y, self.parent_or_size[y]));
+        self.parent_or_size[x] += self.parent_or_size[y];
+        self.parent_or_size[y] = x as isize;
+        self.count -= 1;
+        x
+    }
+
+    pub fn find(&self, a: usize) -> usize {
+        let mut leader = a;
+        while self.parent_or_size[leader] >= 0 {
+            leader = self.parent_or_size[leader] as usize;
+        }
+        leader
+    }
+
+    pub fn same(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn size(&self, a: usize) -> usize {
+        -self.parent_or_size[self.find(a)] as usize
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// A point to [`rollback`](Self::rollback) to.
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes every merge made since `snapshot` was taken.
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            let (absorbed, size) = self.history.pop().unwrap();
+            let leader = self.parent_or_size[absorbed] as usize;
+            self.parent_or_size[leader] -= size;
+            self.parent_or_size[absorbed] = size;
+            self.count += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_find_and_size() {
+        let mut dsu = Dsu::new(5);
+        dsu.merge(0, 1);
+        dsu.merge(3, 4);
+        dsu.merge(1, 4);
+
+        assert!(dsu.same(0, 3));
+        assert!(!dsu.same(0, 2));
+        assert_eq!(dsu.size(4), 4);
+        assert_eq!(dsu.size(2), 1);
+        assert_eq!(dsu.count(), 2);
+        assert_eq!(dsu.groups(), vec![vec![0, 1, 3, 4], vec![2]]);
+    }
+
+    #[test]
+    fn test_long_chain_does_not_overflow() {
+        let n = 1_000_000;
+        let mut dsu = Dsu::new(n);
+        // Hand-build a single chain, which merging by size would never produce
+        for i in 1..n {
+            dsu.parent_or_size[i - 1] = i as isize;
+        }
+        dsu.parent_or_size[n - 1] = -(n as isize);
+
+        assert_eq!(dsu.find(0), n - 1);
+        assert_eq!(dsu.parent_or_size[0], (n - 1) as isize);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut dsu = RollbackDsu::new(4);
+        dsu.merge(0, 1);
+        let snapshot = dsu.snapshot();
+
+        dsu.merge(2, 3);
+        dsu.merge(1, 3);
+        dsu.merge(0, 2);
+        assert!(dsu.same(0, 3));
+        assert_eq!(dsu.size(0), 4);
+        assert_eq!(dsu.count(), 1);
+
+        dsu.rollback(snapshot);
+        assert!(dsu.same(0, 1));
+        assert!(!dsu.same(1, 3));
+        assert!(!dsu.same(2, 3));
+        assert_eq!(dsu.size(1), 2);
+        assert_eq!(dsu.size(3), 1);
+        assert_eq!(dsu.count(), 3);
+
+        dsu.rollback(0);
+        assert_eq!(dsu.count(), 4);
+        assert!(!dsu.same(0, 1));
     }
 }
@@ -0,0 +1,222 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point or offset in the plane. On grids `x` is the column and `y` the row, so `y` grows
+/// downwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// Distance moving only along the axes.
+    pub fn manhattan(self, other: Point<T>) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// Distance moving along the axes and diagonally, like a king on a chessboard.
+    #[allow(dead_code)]
+    pub fn chebyshev(self, other: Point<T>) -> T {
+        distance(self.x, other.x).max(distance(self.y, other.y))
+    }
+}
+
+/// `|a - b|` without going below zero for unsigned types.
+fn distance<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl Point<usize> {
+    /// The point moved by `delta`, unless that leaves the non-negative quadrant.
+    pub fn offset(self, delta: Point<isize>) -> Option<Point<usize>> {
+        Some(Point::new(
+            self.x.checked_add_signed(delta.x)?,
+            self.y.checked_add_signed(delta.y)?,
+        ))
+    }
+
+    /// The neighbouring point in `direction`, unless that leaves the non-negative quadrant.
+    pub fn step(self, direction: Direction) -> Option<Point<usize>> {
+        self.offset(direction.delta())
+    }
+
+    pub fn signed(self) -> Point<isize> {
+        Point::new(self.x as isize, self.y as isize)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// Scales both coordinates.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, factor: T) -> Point<T> {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Point<T>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Point<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+/// One of the four orthogonal directions on a grid, with north pointing up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Parses an arrow `^>v<` or a compass letter `NESW`.
+    pub fn from_char(ch: char) -> Option<Direction> {
+        match ch {
+            '^' | 'N' => Some(Direction::North),
+            '>' | 'E' => Some(Direction::East),
+            'v' | 'S' => Some(Direction::South),
+            '<' | 'W' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// The offset of one step in this direction.
+    pub fn delta(self) -> Point<isize> {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    #[allow(dead_code)]
+    pub fn reverse(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(1, 4);
+
+        assert_eq!(a + b, Point::new(4, 2));
+        assert_eq!(a - b, Point::new(2, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+        assert_eq!(b * 3, Point::new(3, 12));
+
+        let mut c = a;
+        c += b;
+        c -= Point::new(1, 1);
+        assert_eq!(c, Point::new(3, 1));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1usize, 7);
+        let b = Point::new(4usize, 2);
+
+        assert_eq!(a.manhattan(b), 8);
+        assert_eq!(b.manhattan(a), 8);
+        assert_eq!(a.chebyshev(b), 5);
+        assert_eq!(Point::new(-1, -1).manhattan(Point::new(2, 3)), 7);
+    }
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().delta(), -direction.delta());
+        }
+    }
+
+    #[test]
+    fn test_from_char() {
+        assert_eq!(Direction::from_char('^'), Some(Direction::North));
+        assert_eq!(Direction::from_char('v'), Some(Direction::South));
+        assert_eq!(Direction::from_char('W'), Some(Direction::West));
+        assert_eq!(Direction::from_char('>'), Some(Direction::East));
+        assert_eq!(Direction::from_char('x'), None);
+    }
+
+    #[test]
+    fn test_checked_steps() {
+        let origin = Point::new(0usize, 0);
+
+        assert_eq!(origin.step(Direction::North), None);
+        assert_eq!(origin.step(Direction::West), None);
+        assert_eq!(origin.step(Direction::South), Some(Point::new(0, 1)));
+        assert_eq!(
+            Point::new(2usize, 2).offset(Point::new(-2, 1)),
+            Some(Point::new(0, 3))
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use crate::aoc::parse::ParseError;
+use crate::utils::geom::{Direction, Point};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// The eight steps to the orthogonal and diagonal neighbours, clockwise from north.
+#[allow(dead_code)]
+pub const ADJACENT: [Point<isize>; 8] = [
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+    Point::new(-1, -1),
+];
+
+/// A rectangular grid stored row by row in one vector. Cells are addressed by points whose
+/// `x` is the column and `y` the row, starting at the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `height` rows of `width` cells, from cells given row by row.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one row per line, mapping every character with `cell`. A character it rejects
+    /// is reported as `expected`, and every row must be as wide as the first.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for line in input.lines() {
+            let start = cells.len();
+            for (index, ch) in line.char_indices() {
+                let value =
+                    cell(ch).ok_or_else(|| ParseError::at(input, &line[index..], expected))?;
+                cells.push(value);
+            }
+
+            let row_width = cells.len() - start;
+            let width = *width.get_or_insert(row_width);
+            if row_width != width {
+                let column = line
+                    .char_indices()
+                    .nth(width)
+                    .map_or(line.len(), |(i, _)| i);
+                return Err(ParseError::at(
+                    input,
+                    &line[column..],
+                    format!("a row of {} cells", width),
+                ));
+            }
+            height += 1;
+        }
+
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, position: Point<usize>) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Point<usize>) -> Option<&mut T> {
+        self.index_of(position)
+            .map(move |index| &mut self.cells[index])
+    }
+
+    /// Whether a signed position, e.g. one computed by stepping off an edge, is on the grid.
+    pub fn contains(&self, position: Point<isize>) -> bool {
+        position.x >= 0
+            && position.y >= 0
+            && (position.x as usize) < self.width
+            && (position.y as usize) < self.height
+    }
+
+    /// The position `delta` away, if it is still on the grid.
+    pub fn step(&self, position: Point<usize>, delta: Point<isize>) -> Option<Point<usize>> {
+        position
+            .offset(delta)
+            .filter(|&next| self.index_of(next).is_some())
+    }
+
+    /// The orthogonal neighbours of a position that are on the grid, clockwise from north.
+    pub fn neighbours4(&self, position: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction.delta()))
+    }
+
+    /// The orthogonal and diagonal neighbours of a position that are on the grid.
+    #[allow(dead_code)]
+    pub fn neighbours8(&self, position: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        ADJACENT
+            .into_iter()
+            .filter_map(move |delta| self.step(position, delta))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point<usize>> {
+        points(self.width, self.height)
+    }
+
+    /// Every position with its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` from panicking on a grid without columns.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The first position, row by row, holding `value`.
+    pub fn find(&self, value: &T) -> Option<Point<usize>>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point<usize>> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |&(_, cell)| cell == value)
+            .map(|(position, _)| position)
+    }
+
+    #[allow(dead_code)]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// The grid mirrored along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |position| {
+            Point::new(position.y, position.x)
+        })
+    }
+
+    /// The grid turned a quarter turn clockwise.
+    #[allow(dead_code)]
+    pub fn rotate(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(height, self.width, |position| {
+            Point::new(position.y, height - 1 - position.x)
+        })
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(width, self.height, |position| {
+            Point::new(width - 1 - position.x, position.y)
+        })
+    }
+
+    /// A `width` by `height` grid whose cell at each position is the cell of this grid at
+    /// `source(position)`.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(Point<usize>) -> Point<usize>,
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = points(width, height)
+            .map(|position| self[source(position)].clone())
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    /// Where a position is in row by row order, e.g. to key a dense visited set.
+    pub fn index_of(&self, position: Point<usize>) -> Option<usize> {
+        (position.x < self.width && position.y < self.height)
+            .then(|| position.y * self.width + position.x)
+    }
+}
+
+/// The points of a `width` by `height` rectangle at the origin, row by row.
+fn points(width: usize, height: usize) -> impl Iterator<Item = Point<usize>> {
+    (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+}
+
+impl Grid<char> {
+    /// Parses a grid of characters, accepting any character.
+    pub fn chars(input: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(input, Some, "a character")
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point<usize>) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside the {}x{} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, position: Point<usize>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside the {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef";
+
+    #[test]
+    fn test_parse_is_rectangular() {
+        let grid = Grid::chars(INPUT).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(0, 1)], 'd');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert_eq!(grid.to_string(), INPUT);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Grid::parse("12\n3x", |ch| ch.to_digit(10), "a digit").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "a digit");
+
+        let error = Grid::chars("abc\nde\nfgh").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "a row of 3 cells");
+
+        let error = Grid::chars("ab\ncde").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::chars(INPUT).unwrap();
+
+        assert_eq!(
+            grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4(Point::new(1, 1)).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(2, 1), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 5);
+        assert_eq!(grid.step(Point::new(2, 1), Point::new(1, 0)), None);
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::chars("a.b\n.a.").unwrap();
+
+        assert_eq!(grid.find(&'b'), Some(Point::new(2, 0)));
+        assert_eq!(grid.find(&'z'), None);
+        assert_eq!(
+            grid.find_all(&'a').collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid = Grid::chars(INPUT).unwrap();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate().to_string(), "da\neb\nfc");
+        assert_eq!(grid.flip().to_string(), "cba\nfed");
+        assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid = Grid::chars("").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+}
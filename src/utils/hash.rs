@@ -0,0 +1,110 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// A `HashMap` with [`FxHasher`]; create one with `FxHashMap::default()`.
+pub type FxHashMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// A `HashSet` with [`FxHasher`]; create one with `FxHashSet::default()`.
+pub type FxHashSet<T> = HashSet<T, FxBuildHasher>;
+
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+/// The hasher rustc uses for its own tables: one rotate, xor and multiply per word. It is
+/// much faster than the default SipHash on small keys like points and tuples, but makes no
+/// attempt to resist keys chosen to collide, which puzzle input never is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    #[inline]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+
+        let mut rest = chunks.remainder();
+        if rest.len() >= 4 {
+            self.add_to_hash(u32::from_le_bytes(rest[..4].try_into().unwrap()) as u64);
+            rest = &rest[4..];
+        }
+        if rest.len() >= 2 {
+            self.add_to_hash(u16::from_le_bytes(rest[..2].try_into().unwrap()) as u64);
+            rest = &rest[2..];
+        }
+        if let Some(&byte) = rest.first() {
+            self.add_to_hash(byte as u64);
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::{BuildHasher, Hash};
+
+    fn hash<T: Hash>(value: T) -> u64 {
+        FxBuildHasher::default().hash_one(value)
+    }
+
+    #[test]
+    fn test_hash_is_deterministic() {
+        assert_eq!(hash((3usize, 4usize)), hash((3usize, 4usize)));
+        assert_ne!(hash((3usize, 4usize)), hash((4usize, 3usize)));
+        assert_ne!(hash("abc"), hash("abd"));
+        // Byte strings of every remainder length take part in the hash
+        assert_ne!(hash("abcdefghijk"), hash("abcdefghijl"));
+    }
+
+    #[test]
+    fn test_collections() {
+        let mut map: FxHashMap<[i64; 4], i64> = FxHashMap::default();
+        *map.entry([-1, 2, 0, 3]).or_default() += 5;
+        *map.entry([-1, 2, 0, 3]).or_default() += 2;
+        assert_eq!(map[&[-1, 2, 0, 3]], 7);
+
+        let set: FxHashSet<u32> = (0..1000).map(|i| i % 100).collect();
+        assert_eq!(set.len(), 100);
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::{Range, Sub};
+
+/// A set of values stored as sorted, disjoint half-open ranges. Ranges that overlap or touch
+/// are coalesced on insert, so e.g. the free space on a disk is one range per gap.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    /// Start of each range to its end.
+    ranges: BTreeMap<T, T>,
+    /// Starts of the ranges of each length, so fits don't scan every range.
+    by_length: BTreeMap<T, BTreeSet<T>>,
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            ranges: BTreeMap::new(),
+            by_length: BTreeMap::new(),
+        }
+    }
+
+    /// Number of disjoint ranges, not of values.
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Adds every value of `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        if let Some((&before, &before_end)) = self.ranges.range(..=start).next_back() {
+            if before_end >= start {
+                start = before;
+                end = end.max(before_end);
+            }
+        }
+
+        let absorbed: Vec<T> = self.ranges.range(start..=end).map(|(&s, _)| s).collect();
+        for key in absorbed {
+            end = end.max(self.take(key));
+        }
+        self.add(start, end);
+    }
+
+    /// Takes every value of `range` out, splitting a range that only partly overlaps it.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let overlapping: Vec<(T, T)> = self.overlapping_ranges(&range).collect();
+        for (start, end) in overlapping {
+            self.take(start);
+            if start < range.start {
+                self.add(start, range.start);
+            }
+            if end > range.end {
+                self.add(range.end, end);
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value < end)
+    }
+
+    /// The ranges in increasing order.
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// The parts of the set that fall within `range`, in increasing order.
+    #[allow(dead_code)]
+    pub fn overlapping(&self, range: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        self.overlapping_ranges(&range)
+            .map(move |(start, end)| start.max(range.start)..end.min(range.end))
+    }
+
+    /// The lowest range holding at least `length` values. Only the lowest range of each
+    /// length that fits is looked at, so this takes time in the number of distinct lengths
+    /// rather than of ranges.
+    pub fn first_fit(&self, length: T) -> Option<Range<T>> {
+        self.by_length
+            .range(length..)
+            .filter_map(|(_, starts)| starts.first())
+            .min()
+            .map(|&start| start..self.ranges[&start])
+    }
+
+    /// The shortest range holding at least `length` values, the lowest of equally short ones.
+    #[allow(dead_code)]
+    pub fn best_fit(&self, length: T) -> Option<Range<T>> {
+        self.by_length
+            .range(length..)
+            .find_map(|(_, starts)| starts.first())
+            .map(|&start| start..self.ranges[&start])
+    }
+
+    fn add(&mut self, start: T, end: T) {
+        self.ranges.insert(start, end);
+        self.by_length.entry(end - start).or_default().insert(start);
+    }
+
+    /// Removes the range beginning at `start`, returning its end.
+    fn take(&mut self, start: T) -> T {
+        let end = self.ranges.remove(&start).unwrap();
+        let length = end - start;
+        let starts = self.by_length.get_mut(&length).unwrap();
+        starts.remove(&start);
+        if starts.is_empty() {
+            self.by_length.remove(&length);
+        }
+        end
+    }
+
+    fn overlapping_ranges(&self, range: &Range<T>) -> impl Iterator<Item = (T, T)> + '_ {
+        let before = self
+            .ranges
+            .range(..range.start)
+            .next_back()
+            .filter(|(_, &end)| end > range.start);
+        let within = self.ranges.range(range.start..range.end.max(range.start));
+        before
+            .into_iter()
+            .chain(within)
+            .map(|(&start, &end)| (start, end))
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_coalesces() {
+        let mut set = IntervalSet::new();
+        set.insert(5..8);
+        set.insert(1..3);
+        set.insert(3..4);
+        set.insert(10..12);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..4, 5..8, 10..12]);
+
+        set.insert(7..10);
+        set.insert(0..0);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..4, 5..12]);
+        assert_eq!(set.len(), 2);
+
+        set.insert(0..20);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..20]);
+    }
+
+    #[test]
+    fn test_remove_splits() {
+        let mut set: IntervalSet<i32> = [0..10, 20..30].into_iter().collect();
+        set.remove(5..22);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..5, 22..30]);
+
+        set.remove(24..26);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..5, 22..24, 26..30]);
+
+        set.remove(-5..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_queries() {
+        let set: IntervalSet<i32> = [-3..0, 4..6, 10..15].into_iter().collect();
+
+        assert!(set.contains(-3));
+        assert!(!set.contains(0));
+        assert!(set.contains(14));
+        assert!(!set.contains(15));
+        assert_eq!(
+            set.overlapping(-1..12).collect::<Vec<_>>(),
+            vec![-1..0, 4..6, 10..12]
+        );
+        assert_eq!(set.overlapping(6..10).count(), 0);
+    }
+
+    #[test]
+    fn test_fits() {
+        let set: IntervalSet<usize> = [0..4, 6..8, 10..13].into_iter().collect();
+
+        assert_eq!(set.first_fit(2), Some(0..4));
+        assert_eq!(set.best_fit(2), Some(6..8));
+        assert_eq!(set.best_fit(3), Some(10..13));
+        assert_eq!(set.first_fit(5), None);
+    }
+
+    #[test]
+    fn test_fits_follow_changes() {
+        let mut set: IntervalSet<usize> = (0..40).map(|i| i * 10..i * 10 + i % 7 + 1).collect();
+        for step in 0..60 {
+            let start = step * 37 % 400;
+            if step % 3 == 0 {
+                set.insert(start..start + step % 5);
+            } else {
+                set.remove(start..start + step % 4 + 1);
+            }
+
+            for length in 1..10 {
+                let scan = |shortest: bool| {
+                    let fits = set.iter().filter(|range| range.len() >= length);
+                    if shortest {
+                        fits.min_by_key(|range| range.len())
+                    } else {
+                        fits.min_by_key(|range| range.start)
+                    }
+                };
+                assert_eq!(set.first_fit(length), scan(false));
+                assert_eq!(set.best_fit(length), scan(true));
+            }
+        }
+    }
+}
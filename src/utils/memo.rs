@@ -0,0 +1,184 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// A cache for the results of a recursive function, keyed by its arguments. The function
+/// takes the memo as an argument and computes through [`Memo::get_or_insert_with`], which
+/// hands the memo back for the recursive calls:
+///
+/// ```ignore
+/// fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+///     if n < 2 {
+///         return n;
+///     }
+///     memo.get_or_insert_with(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+/// }
+/// ```
+///
+/// A bounded memo evicts the least recently used entry once it is full.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    /// Each cached value with the tick of its last use.
+    entries: HashMap<K, (V, u64)>,
+    /// The keys by the tick of their last use, only kept when bounded.
+    recency: BTreeMap<u64, K>,
+    capacity: Option<usize>,
+    tick: u64,
+    stats: Stats,
+}
+
+/// How a [`Memo`] has been used so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries currently cached.
+    pub entries: usize,
+    /// Entries dropped to stay within the capacity.
+    pub evictions: u64,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    /// A memo that keeps every result.
+    pub fn new() -> Self {
+        Memo {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            capacity: None,
+            tick: 0,
+            stats: Stats::default(),
+        }
+    }
+
+    /// A memo holding at most `capacity` results.
+    #[allow(dead_code)]
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "a memo needs room for at least one entry");
+        Memo {
+            capacity: Some(capacity),
+            ..Memo::new()
+        }
+    }
+
+    /// The cached value for `key`, or else the value `compute` returns, which is cached.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = compute(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// The cached value for `key`, counted as a hit or a miss.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        let tick = self.tick;
+        let Some((value, used)) = self.entries.get_mut(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+
+        self.stats.hits += 1;
+        if self.capacity.is_some() {
+            self.recency.remove(used);
+            self.recency.insert(tick, key.clone());
+        }
+        *used = tick;
+        Some(value.clone())
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.tick += 1;
+        if let Some(capacity) = self.capacity {
+            if let Some((_, used)) = self.entries.get(&key) {
+                self.recency.remove(used);
+            } else if self.entries.len() == capacity {
+                let (_, oldest) = self.recency.pop_first().unwrap();
+                self.entries.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+            self.recency.insert(self.tick, key.clone());
+        }
+        self.entries.insert(key, (value, self.tick));
+    }
+
+    #[allow(dead_code)]
+    pub fn stats(&self) -> Stats {
+        Stats {
+            entries: self.entries.len(),
+            ..self.stats
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+    }
+
+    #[test]
+    fn test_recursion_and_stats() {
+        let mut memo = Memo::new();
+
+        assert_eq!(fib(90, &mut memo), 2_880_067_194_370_816_120);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 87,
+                misses: 89,
+                entries: 89,
+                evictions: 0
+            }
+        );
+
+        assert_eq!(fib(90, &mut memo), 2_880_067_194_370_816_120);
+        assert_eq!(memo.stats().hits, 88);
+    }
+
+    #[test]
+    fn test_bounded_evicts_least_recently_used() {
+        let mut memo = Memo::bounded(2);
+        memo.insert('a', 1);
+        memo.insert('b', 2);
+        assert_eq!(memo.get(&'a'), Some(1));
+
+        memo.insert('c', 3);
+        assert_eq!(memo.get(&'b'), None);
+        assert_eq!(memo.get(&'a'), Some(1));
+        assert_eq!(memo.get(&'c'), Some(3));
+
+        memo.insert('c', 4);
+        assert_eq!(memo.get(&'c'), Some(4));
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 4,
+                misses: 1,
+                entries: 2,
+                evictions: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_bounded_recursion_still_computes() {
+        let mut memo = Memo::bounded(3);
+
+        assert_eq!(fib(40, &mut memo), 102_334_155);
+        assert!(memo.stats().entries <= 3);
+        assert!(memo.stats().evictions > 0);
+    }
+}
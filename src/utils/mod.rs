@@ -1,4 +1,15 @@
+// Shared building blocks for the days. Helpers kept for future days without a caller yet
+// are allowed to be dead one by one, so the lint still catches everything else.
+pub(crate) mod bitset;
+pub(crate) mod counter;
 pub(crate) mod dsu;
-pub(crate) mod maps;
+pub(crate) mod geom;
+pub(crate) mod grid;
+pub(crate) mod hash;
+pub(crate) mod intervals;
+pub(crate) mod memo;
+pub(crate) mod num;
+pub(crate) mod search;
+pub(crate) mod sparse_grid;
 pub(crate) mod strings;
 pub(crate) mod vecs;
@@ -0,0 +1,465 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Everything here works in i128, so products of two i64 values never overflow on the way
+// to a result that fits in an i64 again.
+
+#[allow(dead_code)]
+pub fn gcd(a: i64, b: i64) -> i64 {
+    to_i64(gcd128(a as i128, b as i128))
+}
+
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let (a, b) = (a as i128, b as i128);
+    to_i64((a / gcd128(a, b) * b).abs())
+}
+
+/// The extended Euclidean algorithm: `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`.
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = egcd128(a as i128, b as i128);
+    (to_i64(g), to_i64(x), to_i64(y))
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+#[allow(dead_code)]
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "the modulus must be positive, not {}", m);
+    let (g, x, _) = egcd128(a as i128, m as i128);
+    (g == 1).then(|| to_i64(x.rem_euclid(m as i128)))
+}
+
+/// Solves `x ≡ r (mod m)` for every `(r, m)` at once, returning the smallest non-negative
+/// `x` and the lcm of the moduli, which need not be coprime. `None` when the congruences
+/// contradict each other.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut modulus: i128 = 1;
+
+    for &(r, m) in congruences {
+        assert!(m > 0, "the modulus must be positive, not {}", m);
+        let (r, m) = ((r as i128).rem_euclid(m as i128), m as i128);
+
+        // x + modulus * k ≡ r (mod m) needs gcd(modulus, m) to divide r - x
+        let (g, p, _) = egcd128(modulus, m);
+        if (r - x) % g != 0 {
+            return None;
+        }
+        let step = m / g;
+        let k = ((r - x) / g % step * (p % step)).rem_euclid(step);
+        x += modulus * k;
+        modulus *= step;
+        x = x.rem_euclid(modulus);
+    }
+
+    Some((to_i64(x), to_i64(modulus)))
+}
+
+fn gcd128(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn egcd128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+fn to_i64(value: i128) -> i64 {
+    i64::try_from(value).unwrap_or_else(|_| panic!("{} does not fit in an i64", value))
+}
+
+/// An exact fraction, always in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Ratio {
+    #[allow(dead_code)]
+    pub const ZERO: Ratio = Ratio {
+        numerator: 0,
+        denominator: 1,
+    };
+
+    pub fn new(numerator: i128, denominator: i128) -> Ratio {
+        assert!(denominator != 0, "a ratio cannot have a zero denominator");
+        let sign = denominator.signum();
+        let g = gcd128(numerator, denominator).max(1);
+        Ratio {
+            numerator: sign * numerator / g,
+            denominator: sign * denominator / g,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    #[allow(dead_code)]
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.numerator == 0
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+}
+
+impl From<i64> for Ratio {
+    fn from(value: i64) -> Ratio {
+        Ratio::new(value as i128, 1)
+    }
+}
+
+impl Add for Ratio {
+    type Output = Ratio;
+
+    fn add(self, other: Ratio) -> Ratio {
+        Ratio::new(
+            self.numerator * other.denominator + other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Sub for Ratio {
+    type Output = Ratio;
+
+    fn sub(self, other: Ratio) -> Ratio {
+        self + -other
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Ratio;
+
+    fn neg(self) -> Ratio {
+        Ratio {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Ratio;
+
+    fn mul(self, other: Ratio) -> Ratio {
+        Ratio::new(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Div for Ratio {
+    type Output = Ratio;
+
+    fn div(self, other: Ratio) -> Ratio {
+        Ratio::new(
+            self.numerator * other.denominator,
+            self.denominator * other.numerator,
+        )
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Ratio) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Ratio) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// An augmented matrix `[A | b]` brought to reduced row echelon form by exact elimination.
+struct Echelon {
+    rows: Vec<Vec<Ratio>>,
+    /// The column of the leading 1 of each non-zero row, in order.
+    pivots: Vec<usize>,
+    consistent: bool,
+}
+
+fn eliminate(matrix: &[Vec<i64>], rhs: &[i64]) -> Echelon {
+    assert_eq!(matrix.len(), rhs.len(), "every row needs a right-hand side");
+    let columns = matrix.first().map_or(0, Vec::len);
+    let mut rows: Vec<Vec<Ratio>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &b)| {
+            assert_eq!(
+                row.len(),
+                columns,
+                "every row needs {} coefficients",
+                columns
+            );
+            row.iter().chain([&b]).map(|&v| Ratio::from(v)).collect()
+        })
+        .collect();
+
+    let mut pivots = Vec::new();
+    for column in 0..columns {
+        let top = pivots.len();
+        let Some(found) = (top..rows.len()).find(|&r| !rows[r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(top, found);
+
+        let pivot = rows[top][column];
+        for value in rows[top].iter_mut() {
+            *value = *value / pivot;
+        }
+        let pivot_row = rows[top].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if r != top && !factor.is_zero() {
+                for (value, &p) in row.iter_mut().zip(&pivot_row).skip(column) {
+                    *value = *value - factor * p;
+                }
+            }
+        }
+        pivots.push(column);
+    }
+
+    let consistent = rows[pivots.len()..]
+        .iter()
+        .all(|row| row[columns].is_zero());
+    Echelon {
+        rows,
+        pivots,
+        consistent,
+    }
+}
+
+/// The unique solution of `matrix * x = rhs` as exact fractions, or `None` when there is
+/// no solution or more than one.
+#[allow(dead_code)]
+pub fn solve_linear(matrix: &[Vec<i64>], rhs: &[i64]) -> Option<Vec<Ratio>> {
+    let columns = matrix.first().map_or(0, Vec::len);
+    let echelon = eliminate(matrix, rhs);
+    if !echelon.consistent || echelon.pivots.len() < columns {
+        return None;
+    }
+    Some(
+        echelon
+            .rows
+            .iter()
+            .take(columns)
+            .map(|row| row[columns])
+            .collect(),
+    )
+}
+
+/// Every solution of `matrix * x = rhs` in non-negative integers, for small systems. A
+/// unique solution comes straight from elimination; otherwise each free variable is tried
+/// up to the bound set by a row whose coefficients are all non-negative. `None` when a free
+/// variable has no such row, as its solutions could be endless.
+pub fn nonnegative_solutions(matrix: &[Vec<i64>], rhs: &[i64]) -> Option<Vec<Vec<i64>>> {
+    let columns = matrix.first().map_or(0, Vec::len);
+    let echelon = eliminate(matrix, rhs);
+    if !echelon.consistent {
+        return Some(Vec::new());
+    }
+
+    let free: Vec<usize> = (0..columns)
+        .filter(|column| !echelon.pivots.contains(column))
+        .collect();
+    let bounds = free
+        .iter()
+        .map(|&column| variable_bound(matrix, rhs, column))
+        .collect::<Option<Vec<i64>>>()?;
+    if bounds.iter().any(|&bound| bound < 0) {
+        return Some(Vec::new());
+    }
+
+    let mut solutions = Vec::new();
+    let mut values = vec![0; free.len()];
+    loop {
+        let mut solution = vec![0; columns];
+        for (&column, &value) in free.iter().zip(&values) {
+            solution[column] = value;
+        }
+
+        let valid = echelon.pivots.iter().enumerate().all(|(r, &pivot)| {
+            let row = &echelon.rows[r];
+            let value = free
+                .iter()
+                .zip(&values)
+                .fold(row[columns], |acc, (&column, &v)| {
+                    acc - row[column] * Ratio::from(v)
+                });
+            match value.to_integer() {
+                Some(value) if value >= 0 => {
+                    solution[pivot] = to_i64(value);
+                    true
+                }
+                _ => false,
+            }
+        });
+        if valid {
+            solutions.push(solution);
+        }
+
+        // Advance the free variables like an odometer
+        let Some(position) = (0..values.len()).rev().find(|&i| values[i] < bounds[i]) else {
+            break;
+        };
+        values[position] += 1;
+        values[position + 1..].fill(0);
+    }
+
+    Some(solutions)
+}
+
+/// The largest value `column` can take in a non-negative solution, from the tightest row
+/// whose coefficients are all non-negative. Negative when no solution exists, and `None`
+/// when no row bounds it.
+fn variable_bound(matrix: &[Vec<i64>], rhs: &[i64], column: usize) -> Option<i64> {
+    matrix
+        .iter()
+        .zip(rhs)
+        .filter(|(row, _)| row[column] > 0 && row.iter().all(|&v| v >= 0))
+        .map(|(row, &b)| b.div_euclid(row[column]))
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(101, 103), 10403);
+        assert_eq!(lcm(0, 3), 0);
+    }
+
+    #[test]
+    fn test_egcd_and_inverse() {
+        let (g, x, y) = egcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        // The product overflows an i64 without the wider intermediate
+        let m = 1_000_000_000_000_000_003;
+        let inverse = mod_inverse(2, m).unwrap();
+        assert_eq!((2 * inverse as i128) % m as i128, 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3, 101), (5, 103)]), Some((10305, 10403)));
+        // Moduli sharing a factor
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_ratio() {
+        let half = Ratio::new(2, 4);
+        assert_eq!(half, Ratio::new(-1, -2));
+        assert_eq!((half + Ratio::new(1, 3)).to_string(), "5/6");
+        assert_eq!((half - Ratio::from(1)).to_string(), "-1/2");
+        assert_eq!(half * Ratio::from(4), Ratio::from(2));
+        assert_eq!((half / Ratio::new(3, 4)).to_string(), "2/3");
+        assert_eq!((half * Ratio::from(6)).to_integer(), Some(3));
+        assert_eq!(half.to_integer(), None);
+        assert!(Ratio::new(1, 3) < half);
+    }
+
+    #[test]
+    fn test_solve_linear() {
+        // Day 13's first machine: 94a + 22b = 8400 and 34a + 67b = 5400
+        let matrix = vec![vec![94, 22], vec![34, 67]];
+        assert_eq!(
+            solve_linear(&matrix, &[8400, 5400]),
+            Some(vec![Ratio::from(80), Ratio::from(40)])
+        );
+        assert_eq!(
+            solve_linear(&[vec![2, 0], vec![0, 4]], &[1, 1]),
+            Some(vec![Ratio::new(1, 2), Ratio::new(1, 4)])
+        );
+        assert_eq!(solve_linear(&[vec![1, 1], vec![2, 2]], &[1, 2]), None);
+        assert_eq!(solve_linear(&[vec![1, 1], vec![1, 1]], &[1, 2]), None);
+    }
+
+    #[test]
+    fn test_nonnegative_solutions() {
+        let matrix = vec![vec![94, 22], vec![34, 67]];
+        assert_eq!(
+            nonnegative_solutions(&matrix, &[8400, 5400]),
+            Some(vec![vec![80, 40]])
+        );
+        // Day 13's second machine has no whole solution
+        assert_eq!(
+            nonnegative_solutions(&[vec![26, 67], vec![66, 21]], &[12748, 12176]),
+            Some(vec![])
+        );
+
+        // Large right-hand sides stay exact
+        let offset = 10_000_000_000_000;
+        assert_eq!(
+            nonnegative_solutions(
+                &[vec![26, 67], vec![66, 21]],
+                &[12748 + offset, 12176 + offset]
+            ),
+            Some(vec![vec![118679050709, 103199174542]])
+        );
+
+        // Collinear buttons leave a free variable: 2a + 4b = 12 twice over
+        assert_eq!(
+            nonnegative_solutions(&[vec![2, 4], vec![1, 2]], &[12, 6]),
+            Some(vec![vec![6, 0], vec![4, 1], vec![2, 2], vec![0, 3]])
+        );
+        assert_eq!(nonnegative_solutions(&[vec![1, 1]], &[-1]), Some(vec![]));
+        // a - b = 0 holds for every a = b, with nothing to bound them
+        assert_eq!(nonnegative_solutions(&[vec![1, -1]], &[0]), None);
+    }
+}
@@ -0,0 +1,393 @@
+use crate::utils::hash::{FxHashMap, FxHashSet};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Everything a search learned about the states it reached from its starts.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    /// Cost of the cheapest path from any start to each reached state.
+    pub distances: FxHashMap<S, C>,
+    /// The states a cheapest path to each reached state comes from: the first one found, or
+    /// every one when the search was asked for all shortest paths. Starts have none.
+    pub predecessors: FxHashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    /// A cheapest path from a start to `end`, both included.
+    pub fn path(&self, end: &S) -> Option<Vec<S>> {
+        self.distances.get(end)?;
+
+        let mut path = vec![end.clone()];
+        while let Some(previous) = self
+            .predecessors
+            .get(path.last().unwrap())
+            .and_then(|states| states.first())
+        {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state on some cheapest path from a start to one of `ends`. Only complete when
+    /// the search recorded all predecessors.
+    pub fn on_shortest_paths(&self, ends: impl IntoIterator<Item = S>) -> FxHashSet<S> {
+        let mut seen = FxHashSet::default();
+        let mut stack: Vec<S> = ends
+            .into_iter()
+            .filter(|end| self.distances.contains_key(end))
+            .collect();
+
+        while let Some(state) = stack.pop() {
+            if let Some(previous) = self.predecessors.get(&state) {
+                stack.extend(previous.iter().filter(|p| !seen.contains(*p)).cloned());
+            }
+            seen.insert(state);
+        }
+        seen
+    }
+}
+
+/// Breadth-first search from `starts`, where every step costs 1.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    breadth_first(starts, neighbours, false)
+}
+
+/// Like [`bfs`], but records every predecessor on a shortest path.
+#[allow(dead_code)]
+pub fn bfs_all<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    breadth_first(starts, neighbours, true)
+}
+
+fn breadth_first<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    all: bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search {
+        distances: FxHashMap::default(),
+        predecessors: FxHashMap::default(),
+    };
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        let distance = search.distances[&state] + 1;
+
+        for next in neighbours(&state) {
+            match search.distances.get(&next) {
+                None => {
+                    search.distances.insert(next.clone(), distance);
+                    search
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    queue.push_back(next);
+                }
+                Some(&known) if all && known == distance => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm from `starts` over `neighbours`, which yields each next state with
+/// the cost of the step to it. Costs must not be negative.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    cheapest_first(starts, neighbours, false)
+}
+
+/// Like [`dijkstra`], but records every predecessor on a cheapest path.
+pub fn dijkstra_all<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    cheapest_first(starts, neighbours, true)
+}
+
+fn cheapest_first<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    all: bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search {
+        distances: FxHashMap::default(),
+        predecessors: FxHashMap::default(),
+    };
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        search.distances.insert(start.clone(), C::default());
+        heap.push(Entry {
+            priority: C::default(),
+            state: start,
+        });
+    }
+
+    while let Some(Entry { priority, state }) = heap.pop() {
+        // A cheaper path to the state was found after this entry was queued
+        if priority > search.distances[&state] {
+            continue;
+        }
+
+        for (next, step) in neighbours(&state) {
+            let cost = priority + step;
+            match search.distances.get(&next) {
+                Some(&known) if known < cost => {}
+                Some(&known) if known == cost => {
+                    if all {
+                        search
+                            .predecessors
+                            .entry(next)
+                            .or_default()
+                            .push(state.clone());
+                    }
+                }
+                _ => {
+                    search.distances.insert(next.clone(), cost);
+                    search
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    heap.push(Entry {
+                        priority: cost,
+                        state: next,
+                    });
+                }
+            }
+        }
+    }
+
+    search
+}
+
+/// A* search from `start` to the first state satisfying `is_goal`, returning its cost and
+/// the path to it. `heuristic` must never overestimate the remaining cost.
+pub fn astar<S, C, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search {
+        distances: FxHashMap::from_iter([(start.clone(), C::default())]),
+        predecessors: FxHashMap::default(),
+    };
+    let mut heap = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        state: start,
+    }]);
+
+    while let Some(Entry { state, .. }) = heap.pop() {
+        let cost = search.distances[&state];
+        if is_goal(&state) {
+            let path = search.path(&state)?;
+            return Some((cost, path));
+        }
+
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if search
+                .distances
+                .get(&next)
+                .map_or(true, |&known| next_cost < known)
+            {
+                search.distances.insert(next.clone(), next_cost);
+                search
+                    .predecessors
+                    .insert(next.clone(), vec![state.clone()]);
+                heap.push(Entry {
+                    priority: next_cost + heuristic(&next),
+                    state: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// A queued state, ordered so that the binary max-heap pops the lowest priority first.
+struct Entry<S, C> {
+    priority: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A diamond 0 -> {1, 2} -> 3 -> 4, where both routes to 3 are equally long.
+    fn diamond(state: &u32) -> Vec<u32> {
+        match state {
+            0 => vec![1, 2],
+            1 | 2 => vec![3],
+            3 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let search = bfs([0], diamond);
+
+        assert_eq!(search.distance(&3), Some(2));
+        assert_eq!(search.distance(&4), Some(3));
+        assert_eq!(search.distance(&5), None);
+        assert_eq!(search.path(&4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(search.predecessors[&3], vec![1]);
+    }
+
+    #[test]
+    fn test_bfs_all_predecessors() {
+        let search = bfs_all([0], diamond);
+
+        assert_eq!(search.predecessors[&3], vec![1, 2]);
+        assert_eq!(
+            search.on_shortest_paths([4]),
+            FxHashSet::from_iter([0, 1, 2, 3, 4])
+        );
+        assert_eq!(search.on_shortest_paths([1]), FxHashSet::from_iter([0, 1]));
+    }
+
+    #[test]
+    fn test_bfs_from_several_starts() {
+        let search = bfs([3, 0], diamond);
+
+        assert_eq!(search.distance(&4), Some(1));
+        assert_eq!(search.path(&4), Some(vec![3, 4]));
+    }
+
+    /// 0 -> 1 costs 1, 0 -> 2 costs 4, 1 -> 2 costs 2 and 2 -> 3 costs 1.
+    fn weighted(state: &u32) -> Vec<(u32, u32)> {
+        match state {
+            0 => vec![(1, 1), (2, 4)],
+            1 => vec![(2, 2)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra([0], weighted);
+
+        assert_eq!(search.distance(&2), Some(3));
+        assert_eq!(search.distance(&3), Some(4));
+        assert_eq!(search.path(&3), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_dijkstra_all_keeps_ties() {
+        // Both 0 -> 2 and 0 -> 1 -> 2 cost 3 when the direct step costs 3
+        let search = dijkstra_all([0], |&state: &u32| match state {
+            0 => vec![(1, 1), (2, 3)],
+            1 => vec![(2, 2)],
+            _ => vec![],
+        });
+
+        assert_eq!(search.distance(&2), Some(3));
+        assert_eq!(
+            search.on_shortest_paths([2]),
+            FxHashSet::from_iter([0, 1, 2])
+        );
+    }
+
+    #[test]
+    fn test_astar() {
+        // Walk a number line from 0 to 10 in steps of 1 or 3
+        let found = astar(
+            0i32,
+            |&n| [(n + 1, 1), (n + 3, 1)],
+            |&n| (10 - n).max(0) / 3,
+            |&n| n == 10,
+        );
+
+        let (cost, path) = found.unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(path.first(), Some(&0));
+        assert_eq!(path.last(), Some(&10));
+        assert_eq!(path.len(), 5);
+
+        assert_eq!(astar(0u32, weighted, |_| 0, |&n| n == 7), None);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::utils::geom::Point;
+use crate::utils::grid::Grid;
+use crate::utils::hash::FxHashMap;
+use std::fmt;
+
+/// An unbounded grid holding only the cells that were set, at any signed position. Unlike
+/// [`Grid`] it can grow in every direction, so a simulation can run past the input's edges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[allow(dead_code)]
+pub struct SparseGrid<T> {
+    cells: FxHashMap<Point<isize>, T>,
+    /// The smallest and largest coordinates of any cell, kept up to date as cells change.
+    bounds: Option<(Point<isize>, Point<isize>)>,
+}
+
+#[allow(dead_code)]
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: FxHashMap::default(),
+            bounds: None,
+        }
+    }
+
+    /// The cells of a dense grid, at the same positions, leaving out those `is_empty` accepts.
+    pub fn from_grid(grid: &Grid<T>, mut is_empty: impl FnMut(&T) -> bool) -> SparseGrid<T>
+    where
+        T: Clone,
+    {
+        let mut sparse = SparseGrid::new();
+        for (position, cell) in grid.iter() {
+            if !is_empty(cell) {
+                sparse.insert(position.signed(), cell.clone());
+            }
+        }
+        sparse
+    }
+
+    /// Number of cells set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, position: Point<isize>) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    pub fn get_mut(&mut self, position: Point<isize>) -> Option<&mut T> {
+        self.cells.get_mut(&position)
+    }
+
+    pub fn contains(&self, position: Point<isize>) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    /// Sets a cell and returns what it held before.
+    pub fn insert(&mut self, position: Point<isize>, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (position, position),
+            Some((min, max)) => (
+                Point::new(min.x.min(position.x), min.y.min(position.y)),
+                Point::new(max.x.max(position.x), max.y.max(position.y)),
+            ),
+        });
+        self.cells.insert(position, value)
+    }
+
+    /// Clears a cell and returns what it held.
+    pub fn remove(&mut self, position: Point<isize>) -> Option<T> {
+        let value = self.cells.remove(&position)?;
+        // Only a cell on the edge of the bounding box can shrink it
+        if let Some((min, max)) = self.bounds {
+            if position.x == min.x
+                || position.y == min.y
+                || position.x == max.x
+                || position.y == max.y
+            {
+                self.bounds = bounding_box(self.cells.keys().copied());
+            }
+        }
+        Some(value)
+    }
+
+    /// Every cell set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<isize>, &T)> {
+        self.cells
+            .iter()
+            .map(|(&position, value)| (position, value))
+    }
+
+    /// The smallest and the largest coordinates of the cells set, both included.
+    pub fn bounds(&self) -> Option<(Point<isize>, Point<isize>)> {
+        self.bounds
+    }
+
+    /// The cells between `min` and `max`, both included.
+    pub fn crop(&self, min: Point<isize>, max: Point<isize>) -> SparseGrid<T>
+    where
+        T: Clone,
+    {
+        let mut cropped = SparseGrid::new();
+        for (position, value) in self.iter() {
+            if (min.x..=max.x).contains(&position.x) && (min.y..=max.y).contains(&position.y) {
+                cropped.insert(position, value.clone());
+            }
+        }
+        cropped
+    }
+
+    /// A dense grid over the bounding box, with `empty` in the cells not set, and the position
+    /// its top left cell has in this grid.
+    pub fn to_grid(&self, empty: T) -> (Grid<T>, Point<isize>)
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounds else {
+            return (Grid::new(0, 0, Vec::new()), Point::default());
+        };
+
+        let size = max - min + Point::new(1, 1);
+        let mut grid = Grid::filled(size.x as usize, size.y as usize, empty);
+        for (position, value) in self.iter() {
+            let offset = position - min;
+            grid[Point::new(offset.x as usize, offset.y as usize)] = value.clone();
+        }
+        (grid, min)
+    }
+
+    /// The bounding box drawn row by row, with `empty` in the cells not set.
+    pub fn render(&self, empty: char) -> String
+    where
+        T: fmt::Display,
+    {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+
+        let mut text = String::new();
+        for y in min.y..=max.y {
+            if y > min.y {
+                text.push('\n');
+            }
+            for x in min.x..=max.x {
+                match self.get(Point::new(x, y)) {
+                    Some(value) => text.push_str(&value.to_string()),
+                    None => text.push(empty),
+                }
+            }
+        }
+        text
+    }
+}
+
+fn bounding_box(
+    mut points: impl Iterator<Item = Point<isize>>,
+) -> Option<(Point<isize>, Point<isize>)> {
+    let first = points.next()?;
+    Some(points.fold((first, first), |(min, max), p| {
+        (
+            Point::new(min.x.min(p.x), min.y.min(p.y)),
+            Point::new(max.x.max(p.x), max.y.max(p.y)),
+        )
+    }))
+}
+
+/// Renders the bounding box with `.` for the cells not set.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render('.'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grows_in_every_direction() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point::new(0, 0), '#');
+        grid.insert(Point::new(-2, 1), 'a');
+        grid.insert(Point::new(1, -1), 'b');
+        assert_eq!(grid.insert(Point::new(0, 0), 'c'), Some('#'));
+
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.get(Point::new(-2, 1)), Some(&'a'));
+        assert_eq!(grid.get(Point::new(5, 5)), None);
+        assert_eq!(grid.bounds(), Some((Point::new(-2, -1), Point::new(1, 1))));
+        assert_eq!(grid.to_string(), "...b\n..c.\na...");
+        assert_eq!(grid.render(' '), "   b\n  c \na   ");
+    }
+
+    #[test]
+    fn test_remove_shrinks_bounds() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Point::new(0, 0), 1);
+        grid.insert(Point::new(3, 2), 2);
+        grid.insert(Point::new(1, 1), 3);
+
+        assert_eq!(grid.remove(Point::new(3, 2)), Some(2));
+        assert_eq!(grid.remove(Point::new(3, 2)), None);
+        assert_eq!(grid.bounds(), Some((Point::new(0, 0), Point::new(1, 1))));
+
+        grid.remove(Point::new(0, 0));
+        grid.remove(Point::new(1, 1));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render('.'), "");
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let dense = Grid::chars("..#\n#..").unwrap();
+        let sparse = SparseGrid::from_grid(&dense, |&ch| ch == '.');
+        assert_eq!(sparse.len(), 2);
+        assert!(sparse.contains(Point::new(2, 0)));
+
+        let (grid, origin) = sparse.to_grid('.');
+        assert_eq!(grid, dense);
+        assert_eq!(origin, Point::new(0, 0));
+
+        let mut shifted = sparse.clone();
+        shifted.insert(Point::new(-1, -1), '@');
+        let (grid, origin) = shifted.to_grid('.');
+        assert_eq!(origin, Point::new(-1, -1));
+        assert_eq!(grid.to_string(), "@...\n...#\n.#..");
+    }
+
+    #[test]
+    fn test_crop() {
+        let dense = Grid::chars("ab\ncd").unwrap();
+        let sparse = SparseGrid::from_grid(&dense, |_| false);
+
+        let cropped = sparse.crop(Point::new(1, 0), Point::new(5, 5));
+        assert_eq!(cropped.to_string(), "b\nd");
+        assert_eq!(cropped.bounds(), Some((Point::new(1, 0), Point::new(1, 1))));
+        assert!(sparse.crop(Point::new(3, 3), Point::new(4, 4)).is_empty());
+    }
+}
@@ -1,47 +1,97 @@
+use crate::aoc::parse::{number, ParseError};
 use std::str::FromStr;
 
-pub fn split_input_into_vector(input: &str) -> Vec<(i32, i32)> {
+pub fn split_input_into_vector(input: &str) -> Result<Vec<(i32, i32)>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let mut split = line
-                .split_whitespace()
-                .map(|num| num.parse::<i32>().unwrap());
-            (split.next().unwrap(), split.next().unwrap())
+            let mut split = line.split_whitespace();
+            let mut next = || {
+                let token = split.next().unwrap_or(&line[line.len()..]);
+                number(input, token)
+            };
+            Ok((next()?, next()?))
         })
         .collect()
 }
 
-pub fn split_element_by_element_array_input_into_vector<T>(input: &str) -> Vec<Vec<T>>
-where
-    T: FromStr,
-    T::Err: std::fmt::Debug,
-{
+pub fn split_array_input_into_vector<T: FromStr>(input: &str) -> Result<Vec<Vec<T>>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let mut vec: Vec<T> = Vec::new();
-            for item in line.chars() {
-                vec.push(item.to_string().parse::<T>().unwrap());
-            }
-            return vec;
+            line.split_whitespace()
+                .map(|item| number(input, item))
+                .collect()
         })
         .collect()
 }
 
-pub fn split_array_input_into_vector<T>(input: &str) -> Vec<Vec<T>>
-where
-    T: FromStr,
-    T::Err: std::fmt::Debug, // Ensures we can unwrap the result of `FromStr`.
-{
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|item| item.parse::<T>().unwrap())
-                .collect()
+/// The text captured by the `{}` placeholders of a [`scan`] pattern, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures<'a> {
+    input: &'a str,
+    fields: Vec<&'a str>,
+}
+
+impl<'a> Captures<'a> {
+    /// The captures as text; the pattern must have exactly `N` placeholders.
+    pub fn fields<const N: usize>(&self) -> [&'a str; N] {
+        self.fields.as_slice().try_into().unwrap_or_else(|_| {
+            panic!(
+                "the pattern has {} placeholders, not {}",
+                self.fields.len(),
+                N
+            )
         })
-        .collect()
+    }
+
+    /// The captures parsed as numbers; the pattern must have exactly `N` placeholders.
+    pub fn numbers<T: FromStr, const N: usize>(&self) -> Result<[T; N], ParseError> {
+        let fields = self.fields::<N>();
+        let mut numbers = Vec::with_capacity(N);
+        for field in fields {
+            numbers.push(number(self.input, field)?);
+        }
+        Ok(numbers.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+}
+
+/// Matches `line`, a slice of `input`, against a pattern like `"p={},{} v={},{}"`. Text
+/// outside the placeholders must appear literally, and each `{}` captures everything up to
+/// the first occurrence of the text after it, or the rest of the line for a trailing `{}`;
+/// placeholders must therefore be separated by some text. Missing text is reported where
+/// it was expected.
+pub fn scan<'a>(input: &'a str, line: &'a str, pattern: &str) -> Result<Captures<'a>, ParseError> {
+    let mut pieces = pattern.split("{}");
+    let prefix = pieces.next().unwrap_or("");
+    let mut rest = line
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(input, line, format!("`{}`", prefix)))?;
+
+    let mut fields = Vec::new();
+    let mut pieces = pieces.peekable();
+    while let Some(literal) = pieces.next() {
+        if literal.is_empty() {
+            assert!(
+                pieces.peek().is_none(),
+                "placeholders in {:?} must be separated by text",
+                pattern
+            );
+            fields.push(rest);
+            rest = &rest[rest.len()..];
+        } else {
+            let (field, after) = rest.split_once(literal).ok_or_else(|| {
+                ParseError::at(input, &rest[rest.len()..], format!("`{}`", literal))
+            })?;
+            fields.push(field);
+            rest = after;
+        }
+    }
+
+    if !rest.is_empty() {
+        return Err(ParseError::at(input, rest, "the end of the line"));
+    }
+    Ok(Captures { input, fields })
 }
 
 #[cfg(test)]
@@ -57,7 +107,7 @@ mod test {
     #[test]
     fn test_split_array_input_into_vector_with_char() {
         let input = "a b c\nd e f\ng h i";
-        let result: Vec<Vec<char>> = super::split_array_input_into_vector(input);
+        let result: Vec<Vec<char>> = super::split_array_input_into_vector(input).unwrap();
         assert_eq!(
             result,
             vec![
@@ -70,48 +120,89 @@ mod test {
 
     #[test]
     fn should_correctly_parse_input() {
-        super::split_input_into_vector(&EXAMPLE1);
+        super::split_input_into_vector(EXAMPLE1).unwrap();
     }
 
     #[test]
     fn test_split_input_into_vector_basic() {
         let input = "3 4\n4 3\n2 5\n1 3\n3 9\n3 3";
         let expected = vec![(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3)];
-        assert_eq!(super::split_input_into_vector(input), expected);
+        assert_eq!(super::split_input_into_vector(input).unwrap(), expected);
     }
 
     #[test]
     fn test_split_input_into_vector_with_extra_spaces() {
         let input = "  3   4  \n  4  3\n  2    5\n1 3   \n  3 9  \n 3   3  ";
         let expected = vec![(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3)];
-        assert_eq!(super::split_input_into_vector(input), expected);
+        assert_eq!(super::split_input_into_vector(input).unwrap(), expected);
     }
 
     #[test]
     fn test_split_input_into_vector_single_line() {
         let input = "10 20";
         let expected = vec![(10, 20)];
-        assert_eq!(super::split_input_into_vector(input), expected);
+        assert_eq!(super::split_input_into_vector(input).unwrap(), expected);
     }
 
     #[test]
     fn test_split_input_into_vector_empty_input() {
         let input = "";
         let expected: Vec<(i32, i32)> = vec![];
-        assert_eq!(super::split_input_into_vector(input), expected);
+        assert_eq!(super::split_input_into_vector(input).unwrap(), expected);
     }
 
     #[test]
-    #[should_panic(expected = "called `Result::unwrap()` on an `Err` value")]
     fn test_split_input_into_vector_invalid_number() {
         let input = "3 a\n4 3";
-        super::split_input_into_vector(input); // This should panic due to invalid number
+        let error = super::split_input_into_vector(input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
     }
 
     #[test]
-    #[should_panic(expected = "called `Option::unwrap()` on a `None` value")]
     fn test_split_input_into_vector_incomplete_pair() {
         let input = "3\n4 3";
-        super::split_input_into_vector(input); // This should panic due to missing second number
+        let error = super::split_input_into_vector(input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 2));
+    }
+
+    #[test]
+    fn test_scan_numbers() {
+        let input = "p=0,4 v=3,-3";
+        let [px, py, vx, vy] = super::scan(input, input, "p={},{} v={},{}")
+            .unwrap()
+            .numbers::<i32, 4>()
+            .unwrap();
+        assert_eq!((px, py, vx, vy), (0, 4, 3, -3));
+    }
+
+    #[test]
+    fn test_scan_fields() {
+        let input = "x00 AND y00 -> z00";
+        let captures = super::scan(input, input, "{} {} {} -> {}").unwrap();
+        assert_eq!(captures.fields(), ["x00", "AND", "y00", "z00"]);
+    }
+
+    #[test]
+    fn test_scan_errors() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22\nPrize: X=8400, Y=5400 extra";
+        let lines: Vec<&str> = input.lines().collect();
+
+        let error = super::scan(input, lines[1], "Button B: X+{}, Y+{}").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 15));
+        assert_eq!(error.expected, "`, Y+`");
+
+        let error = super::scan(input, lines[0], "Button B: X+{}, Y+{}").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "`Button B: X+`");
+
+        let error = super::scan(input, lines[2], "Prize: X={}, Y={} ").unwrap_err();
+        assert_eq!(error.column, 23);
+        assert_eq!(error.expected, "the end of the line");
+
+        let captures = super::scan(input, lines[2], "Prize: X={}, Y={}").unwrap();
+        let error = captures.numbers::<u32, 2>().unwrap_err();
+        assert_eq!((error.line, error.column), (3, 18));
+        assert_eq!(error.expected, "a number");
+        assert!(super::scan(input, lines[0], "Button A: X+{}, Y={}").is_err());
     }
 }
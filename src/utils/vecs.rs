@@ -1,6 +1,6 @@
-pub fn remove_at_index(levels: &Vec<i32>, index: usize) -> Vec<i32> {
+pub fn remove_at_index(levels: &[i32], index: usize) -> Vec<i32> {
     levels
-        .into_iter()
+        .iter()
         .enumerate()
         .filter(|&(i, _)| i != index)
         .map(|(_, val)| *val)